@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use crate::ai::field::Field;
+use crate::algorithm::Grid;
+use crate::constants::ai::{BUMPINESS_WEIGHT, HEIGHT_WEIGHT, HOLES_WEIGHT, LINES_WEIGHT, WELLS_WEIGHT};
+use crate::engine::utility::types::Coordinate;
+
+/**
+ * Score a field by weighting features of its surface
+ */
+
+/// How much each feature of a field contributes to its score
+///
+/// Features that make a field worse should have a negative weight.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Weights {
+  /// sum of the column heights
+  pub height: f32,
+  /// empty cells beneath an occupied cell
+  pub holes: f32,
+  /// sum of the height differences between neighbouring columns
+  pub bumpiness: f32,
+  /// sum of the depths of columns lower than both their neighbours
+  pub wells: f32,
+  /// lines cleared by the placement
+  pub lines: f32,
+}
+
+impl Default for Weights {
+  fn default() -> Self {
+    Self {
+      height: HEIGHT_WEIGHT,
+      holes: HOLES_WEIGHT,
+      bumpiness: BUMPINESS_WEIGHT,
+      wells: WELLS_WEIGHT,
+      lines: LINES_WEIGHT,
+    }
+  }
+}
+
+/// The measured features of a field
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Features {
+  pub height: u32,
+  pub holes: u32,
+  pub bumpiness: u32,
+  pub wells: u32,
+}
+
+/// Measure the features of `field`
+pub fn measure(field: &Field) -> Features {
+  let (width, height) = field.size().destructure();
+  let heights: Vec<u32> = (0..width as i32).map(|x| field.column_height(x)).collect();
+
+  let holes = (0..width as i32)
+    .map(|x| {
+      let top = height - heights[x as usize];
+      (top as i32..height as i32).filter(|y| !field.is_occupied(&Coordinate::new(x, *y))).count() as u32
+    })
+    .sum();
+
+  let bumpiness = heights.windows(2)
+    .map(|pair| pair[0].abs_diff(pair[1]))
+    .sum();
+
+  // the walls count as columns as tall as the field
+  let wells = (0..heights.len())
+    .map(|x| {
+      let left = if x == 0 { height } else { heights[x - 1] };
+      let right = heights.get(x + 1).copied().unwrap_or(height);
+      left.min(right).saturating_sub(heights[x])
+    })
+    .sum();
+
+  Features { height: heights.iter().sum(), holes, bumpiness, wells }
+}
+
+/// Score `field` after a placement that cleared `lines`, higher is better
+pub fn evaluate(field: &Field, lines: u32, weights: &Weights) -> f32 {
+  let Features { height, holes, bumpiness, wells } = measure(field);
+  height as f32 * weights.height
+    + holes as f32 * weights.holes
+    + bumpiness as f32 * weights.bumpiness
+    + wells as f32 * weights.wells
+    + lines as f32 * weights.lines
+}
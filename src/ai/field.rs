@@ -0,0 +1,125 @@
+use crate::algorithm::Grid;
+use crate::constants::piece::Shape;
+use crate::engine::utility::conversion::coordinate_to_index;
+use crate::engine::utility::types::{Coordinate, Size2};
+
+/**
+ * A lightweight copy of a board for simulating placements
+ */
+
+/// The occupied cells of a board, without tile data
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+  size: Size2,
+  cells: Vec<bool>,
+}
+
+impl Field {
+  /// Instantiate an empty field of `size`
+  pub fn new(size: Size2) -> Self {
+    Self { size, cells: vec![false; (size.x * size.y) as usize] }
+  }
+
+  /// Instantiate a field from the occupied cells of `grid`
+  pub fn from_grid(grid: &impl Grid) -> Self {
+    let mut field = Self::new(grid.size());
+    for y in 0..field.size.y as i32 {
+      for x in 0..field.size.x as i32 {
+        let coordinate = Coordinate::new(x, y);
+        if grid.is_occupied(&coordinate) {
+          field.fill(&coordinate);
+        }
+      }
+    }
+    field
+  }
+
+  /// Mark `coordinate` as occupied, ignoring coordinates outside the field
+  pub fn fill(&mut self, coordinate: &Coordinate) {
+    if self.is_bound(coordinate) {
+      let index = coordinate_to_index(coordinate, self.size);
+      self.cells[index] = true;
+    }
+  }
+  /// Mark `coordinate` as empty, ignoring coordinates outside the field
+  pub fn clear(&mut self, coordinate: &Coordinate) {
+    if self.is_bound(coordinate) {
+      let index = coordinate_to_index(coordinate, self.size);
+      self.cells[index] = false;
+    }
+  }
+
+  /// Fill the cells of `shape` at `position` and remove any full lines
+  ///
+  /// returns the number of lines cleared
+  pub fn lock(&mut self, shape: &Shape, position: &Coordinate) -> u32 {
+    for coord in shape {
+      self.fill(&Coordinate::new(position.x + coord.x, position.y + coord.y));
+    }
+    self.clear_lines()
+  }
+
+  /// Remove full lines, moving the lines above them down
+  ///
+  /// returns the number of lines cleared
+  pub fn clear_lines(&mut self) -> u32 {
+    let width = self.size.x as usize;
+    let mut rows: Vec<&[bool]> = self.cells
+      .chunks(width)
+      .filter(|row| !row.iter().all(|cell| *cell))
+      .collect();
+    let cleared = self.size.y as usize - rows.len();
+    if cleared == 0 {
+      return 0;
+    }
+
+    let empty = vec![false; width];
+    let mut cells = Vec::with_capacity(self.cells.len());
+    for _ in 0..cleared {
+      cells.extend_from_slice(&empty);
+    }
+    for row in rows.drain(..) {
+      cells.extend_from_slice(row);
+    }
+    self.cells = cells;
+
+    cleared as u32
+  }
+
+  /// Push every line up by `lines` and fill the bottom lines except for the `hole` column
+  ///
+  /// returns `false` if occupied cells were pushed off the field
+  pub fn add_garbage(&mut self, lines: u32, hole: i32) -> bool {
+    let width = self.size.x as usize;
+    let lines = lines.min(self.size.y) as usize;
+    let overflow = self.cells[..lines * width].iter().any(|cell| *cell);
+
+    self.cells.drain(..lines * width);
+    for _ in 0..lines {
+      self.cells.extend((0..width).map(|x| x as i32 != hole));
+    }
+
+    !overflow
+  }
+
+  /// Get the number of cells from the floor to the highest occupied cell in column `x`
+  pub fn column_height(&self, x: i32) -> u32 {
+    (0..self.size.y as i32)
+      .find(|y| self.is_occupied(&Coordinate::new(x, *y)))
+      .map_or(0, |top| self.size.y - top as u32)
+  }
+}
+
+impl Grid for Field {
+  fn size(&self) -> Size2 {
+    self.size
+  }
+  fn is_bound(&self, coordinate: &Coordinate) -> bool {
+    let x_bound = coordinate.x >= 0 && coordinate.x < self.size.x as i32;
+    let y_bound = coordinate.y >= 0 && coordinate.y < self.size.y as i32;
+    x_bound && y_bound
+  }
+  fn is_occupied(&self, coordinate: &Coordinate) -> bool {
+    self.is_bound(coordinate) && self.cells[coordinate_to_index(coordinate, self.size)]
+  }
+}
@@ -0,0 +1,58 @@
+use crate::ai::evaluate::{evaluate, Weights};
+use crate::ai::field::Field;
+use crate::ai::search::{find_placements, Placement, Pose};
+use crate::algorithm::Grid;
+use crate::constants::piece::{DEFAULT_ROTATION, ShapeType};
+use crate::piece::spawn_position;
+
+/**
+ * Automated player that chooses where to place pieces
+ */
+
+pub mod evaluate;
+pub mod field;
+pub mod search;
+
+/// Score every placement of `shape_type` from `start`, considering where `next` could go after spawning above
+/// `first_row`, the first visible row of `field`
+///
+/// Each placement is scored by the best field the next piece can leave, or by its own field without a next piece.
+pub fn score_placements(field: &Field, shape_type: ShapeType, start: Pose, next: Option<ShapeType>, first_row: i32, weights: &Weights) -> Vec<(Placement, f32)> {
+  let piece = shape_type.data();
+  let next_piece = next.map(|next| (next, next.data()));
+
+  find_placements(field, piece, start)
+    .into_iter()
+    .map(|placement| {
+      let mut after = field.clone();
+      let lines = after.lock(&piece.shape[placement.pose.rotation], &placement.pose.position);
+
+      let next_score = next_piece.and_then(|(next, next_piece)| {
+        let next_start = Pose::new(DEFAULT_ROTATION, spawn_position(&next, field.size().x, first_row));
+        find_placements(&after, next_piece, next_start)
+          .iter()
+          .map(|next_placement| {
+            let mut next_after = after.clone();
+            let next_lines = next_after.lock(&next_piece.shape[next_placement.pose.rotation], &next_placement.pose.position);
+            evaluate(&next_after, next_lines, weights)
+          })
+          .max_by(f32::total_cmp)
+      });
+
+      let score = match next_score {
+        Some(next_score) => next_score + lines as f32 * weights.lines,
+        None => evaluate(&after, lines, weights),
+      };
+      (placement, score)
+    })
+    .collect()
+}
+
+/// Choose the best placement of `shape_type` from `start`, considering where `next` could go after spawning above
+/// `first_row`, the first visible row of `field`
+pub fn best_placement(field: &Field, shape_type: ShapeType, start: Pose, next: Option<ShapeType>, first_row: i32, weights: &Weights) -> Option<Placement> {
+  score_placements(field, shape_type, start, next, first_row, weights)
+    .into_iter()
+    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    .map(|(placement, _)| placement)
+}
@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::ai::field::Field;
+use crate::board::Action;
+use crate::constants::piece::PieceData;
+use crate::engine::utility::types::Coordinate;
+use crate::piece::{evaluate_rotation, evaluate_transform, Piece, RotationResult, Transform, TransformResult};
+
+/**
+ * Find every placement a piece can reach with the boards movement and rotation rules
+ */
+
+/// The rotation and position of a piece
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pose {
+  pub rotation: usize,
+  pub position: Coordinate,
+}
+
+impl Pose {
+  pub const fn new(rotation: usize, position: Coordinate) -> Self {
+    Self { rotation, position }
+  }
+}
+
+impl From<&Piece> for Pose {
+  /// Get the current pose of `piece`
+  fn from(piece: &Piece) -> Self {
+    Self::new(piece.rotation, piece.position)
+  }
+}
+
+/// A resting place for a piece and the actions that reach it
+#[derive(Clone, Debug)]
+pub struct Placement {
+  /// where the piece locks
+  pub pose: Pose,
+  /// each action and the pose it leaves the piece in, ending with a hard drop
+  pub path: Vec<(Action, Pose)>,
+}
+
+/// Actions that keep the piece active, explored from every pose
+const MOVES: [Action; 4] = [Action::MoveLeft, Action::MoveRight, Action::Rotate, Action::SoftDrop];
+
+/// Apply `action` to `piece` at `pose`
+///
+/// returns the new pose, or `None` if the piece could not move
+pub fn step(field: &Field, piece: &PieceData, pose: &Pose, action: Action) -> Option<Pose> {
+  let shape = &piece.shape[pose.rotation];
+  let transform = |transform: Transform| match evaluate_transform(shape, &pose.position, transform, field) {
+    TransformResult::Success { position } => Some(Pose::new(pose.rotation, position)),
+    _ => None,
+  };
+
+  match action {
+    Action::MoveLeft => transform(Transform::Left),
+    Action::MoveRight => transform(Transform::Right),
+    Action::SoftDrop => transform(Transform::Down),
+    Action::Rotate => match evaluate_rotation(piece, pose.rotation, &pose.position, field) {
+      RotationResult::Success { rotation, position } => Some(Pose::new(rotation, position)),
+      RotationResult::Collision => None,
+    },
+    Action::HardDrop => {
+      let mut landing = *pose;
+      while let Some(next) = step(field, piece, &landing, Action::SoftDrop) {
+        landing = next;
+      }
+      Some(landing)
+    }
+  }
+}
+
+/// Find every placement reachable by `piece` starting at `start`
+///
+/// Poses are explored breadth first, so each placement is reached with the fewest actions.
+pub fn find_placements(field: &Field, piece: &PieceData, start: Pose) -> Vec<Placement> {
+  let mut placements = Vec::new();
+  let mut landed = HashSet::new();
+  let mut landings: HashMap<Pose, Pose> = HashMap::new();
+
+  // the action that first reached each pose, and the pose it was taken from
+  let mut parents: HashMap<Pose, Option<(Action, Pose)>> = HashMap::from([(start, None)]);
+  let mut queue = VecDeque::from([start]);
+  while let Some(pose) = queue.pop_front() {
+    // lock from here
+    let landing = find_landing(field, piece, pose, &mut landings);
+    if landed.insert(landing) {
+      let mut path = trace_path(&parents, pose);
+      path.push((Action::HardDrop, landing));
+      placements.push(Placement { pose: landing, path });
+    }
+
+    // keep moving
+    for action in MOVES {
+      if let Some(next) = step(field, piece, &pose, action) {
+        parents.entry(next).or_insert_with(|| {
+          queue.push_back(next);
+          Some((action, pose))
+        });
+      }
+    }
+  }
+
+  placements
+}
+
+/// Find where a piece at `pose` lands, remembering the landing of every pose passed through
+fn find_landing(field: &Field, piece: &PieceData, pose: Pose, landings: &mut HashMap<Pose, Pose>) -> Pose {
+  let mut fallen = Vec::new();
+  let mut current = pose;
+  let landing = loop {
+    if let Some(landing) = landings.get(&current) {
+      break *landing;
+    }
+    fallen.push(current);
+    match step(field, piece, &current, Action::SoftDrop) {
+      Some(next) => current = next,
+      None => break current,
+    }
+  };
+
+  for pose in fallen {
+    landings.insert(pose, landing);
+  }
+  landing
+}
+
+/// Follow the parents of `pose` back to the start, returning the actions taken in order
+fn trace_path(parents: &HashMap<Pose, Option<(Action, Pose)>>, pose: Pose) -> Vec<(Action, Pose)> {
+  let mut path = Vec::new();
+  let mut current = pose;
+  while let Some(Some((action, parent))) = parents.get(&current) {
+    path.push((*action, current));
+    current = *parent;
+  }
+  path.reverse();
+  path
+}
+
+/// Find the fewest actions that move `piece` from `start` and lock it at `target`
+pub fn find_path(field: &Field, piece: &PieceData, start: Pose, target: Pose) -> Option<Placement> {
+  find_placements(field, piece, start)
+    .into_iter()
+    .find(|placement| placement.pose == target)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::constants::piece::ShapeType;
+  use crate::engine::geometry::Vec2;
+
+  const SIZE: Vec2<u32> = Vec2::new(10, 20);
+
+  /// Check every placement is distinct and that following its path from `start` ends where it says it locks
+  fn assert_paths_reach(field: &Field, piece: &PieceData, start: Pose, placements: &[Placement]) {
+    let unique: HashSet<Pose> = placements.iter().map(|placement| placement.pose).collect();
+    assert_eq!(unique.len(), placements.len());
+
+    for placement in placements {
+      let mut pose = start;
+      for (action, expected) in &placement.path {
+        pose = step(field, piece, &pose, *action).expect("path takes an action that can't be made");
+        assert_eq!(pose, *expected);
+      }
+      assert_eq!(placement.path.last().map(|(action, _)| *action), Some(Action::HardDrop));
+      assert_eq!(pose, placement.pose);
+    }
+  }
+
+  #[test]
+  fn reaches_every_column_of_an_empty_field() {
+    let field = Field::new(SIZE);
+    let piece = ShapeType::O.data();
+    let start = Pose::new(0, Coordinate::new(4, 0));
+
+    let placements = find_placements(&field, piece, start);
+    let mut columns: Vec<i32> = placements.iter().map(|placement| placement.pose.position.x).collect();
+    columns.sort();
+    assert_eq!(columns, (0..=8).collect::<Vec<_>>());
+    assert!(placements.iter().all(|placement| placement.pose.position.y == 18));
+    assert_paths_reach(&field, piece, start, &placements);
+  }
+
+  #[test]
+  fn reaches_every_rotation_of_an_empty_field() {
+    let field = Field::new(SIZE);
+    let piece = ShapeType::T.data();
+    let start = Pose::new(0, Coordinate::new(3, 0));
+
+    let placements = find_placements(&field, piece, start);
+    assert_eq!(placements.len(), 8 + 9 + 8 + 9); // flat rotations span 3 columns, upright rotations 2
+    assert_paths_reach(&field, piece, start, &placements);
+  }
+
+  #[test]
+  fn tucks_under_an_overhang() {
+    // a roof over every column but the two on the left
+    let mut field = Field::new(SIZE);
+    for x in 2..SIZE.x as i32 {
+      field.fill(&Coordinate::new(x, 16));
+    }
+    let piece = ShapeType::O.data();
+    let start = Pose::new(0, Coordinate::new(4, 0));
+
+    let placements = find_placements(&field, piece, start);
+    assert_paths_reach(&field, piece, start, &placements);
+
+    // dropped onto the roof
+    assert!(placements.iter().any(|placement| placement.pose == Pose::new(0, Coordinate::new(4, 14))));
+
+    // dropped down the gap, then slid under the roof
+    let tucked = placements.iter()
+      .find(|placement| placement.pose == Pose::new(0, Coordinate::new(8, 18)))
+      .expect("failed to tuck under the overhang");
+    let actions: Vec<Action> = tucked.path.iter().map(|(action, _)| *action).collect();
+    let last_drop = actions.iter().rposition(|action| *action == Action::SoftDrop).unwrap();
+    assert!(actions[last_drop..].contains(&Action::MoveRight));
+  }
+
+  #[test]
+  fn finds_no_path_to_an_unreachable_pose() {
+    let field = Field::new(SIZE);
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    assert!(find_path(&field, ShapeType::O.data(), start, Pose::new(0, Coordinate::new(4, 10))).is_none());
+  }
+}
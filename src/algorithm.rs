@@ -1,23 +1,47 @@
-use crate::constants::game::{DOUBLE_LINE_MULTIPLIER, FALL_COOLDOWN, LEVEL_SPEED_MULTIPLIER, MAX_LINES, MAX_TETRIS_LEVEL, SINGLE_LINE_MULTIPLIER, START_TETRIS_LEVEL, TETRIS_MULTIPLIER, TRIPLE_LINE_MULTIPLIER};
+use crate::constants::game::{DOUBLE_LINE_ATTACK, DOUBLE_LINE_MULTIPLIER, FALL_COOLDOWN, LEVEL_SPEED_MULTIPLIER, MAX_LINES, MAX_TETRIS_LEVEL, SINGLE_LINE_ATTACK, SINGLE_LINE_MULTIPLIER, START_TETRIS_LEVEL, TETRIS_ATTACK, TETRIS_MULTIPLIER, TRIPLE_LINE_ATTACK, TRIPLE_LINE_MULTIPLIER};
 use crate::constants::piece::Shape;
 use crate::engine::tile::tilemap::Tilemap;
-use crate::engine::utility::types::Coordinate;
+use crate::engine::utility::types::{Coordinate, Size2};
 
 /**
  * utility algorithms for Tetris
  */
 
+// Grid //
+
+/// A grid of cells that shapes are bound by and collide with
+pub trait Grid {
+  /// The number of cells across and down the grid
+  fn size(&self) -> Size2;
+  /// Check if `coordinate` is within the bounds of the grid
+  fn is_bound(&self, coordinate: &Coordinate) -> bool;
+  /// Check if `coordinate` is occupied
+  fn is_occupied(&self, coordinate: &Coordinate) -> bool;
+}
+
+impl Grid for Tilemap {
+  fn size(&self) -> Size2 {
+    self.dimensions
+  }
+  fn is_bound(&self, coordinate: &Coordinate) -> bool {
+    Tilemap::is_bound(self, coordinate)
+  }
+  fn is_occupied(&self, coordinate: &Coordinate) -> bool {
+    Tilemap::is_occupied(self, coordinate)
+  }
+}
+
 // Coordinate //
 
-/// Check if a coordinate is above the bounds of a tilemap
-pub fn above_bounds(coordinate: &Coordinate, tilemap: &Tilemap) -> bool {
-  let is_bound_horizontal = coordinate.x >= 0 && coordinate.x < tilemap.dimensions.x as i32;
+/// Check if a coordinate is above the bounds of a grid
+pub fn above_bounds(coordinate: &Coordinate, grid: &impl Grid) -> bool {
+  let is_bound_horizontal = coordinate.x >= 0 && coordinate.x < grid.size().x as i32;
   coordinate.y < 0 && is_bound_horizontal
 }
 
-/// Check if a shape is on the bottom of a tilemap
-pub fn is_shape_on_bottom(coordinate: &Shape, tilemap: &Tilemap) -> bool {
-  let last_row = tilemap.dimensions.y as i32 - 1;
+/// Check if a shape is on the bottom of a grid
+pub fn is_shape_on_bottom(coordinate: &Shape, grid: &impl Grid) -> bool {
+  let last_row = grid.size().y as i32 - 1;
   coordinate.iter()
     .any(|c| c.y >= last_row)
 }
@@ -40,16 +64,16 @@ pub fn get_new_shape_coordinates<'a>(old: &'a Shape, new: &'a Shape) -> Shape {
     .collect()
 }
 
-/// Check if a shape is wholly contained the bounds of a  tilemap
-pub fn check_bounds(shape: &Shape, tilemap: &Tilemap) -> bool {
+/// Check if a shape is wholly contained the bounds of a  grid
+pub fn check_bounds(shape: &Shape, grid: &impl Grid) -> bool {
   shape.iter()
-    .all(|c| above_bounds(c, tilemap) || tilemap.is_bound(c))
+    .all(|c| above_bounds(c, grid) || grid.is_bound(c))
 }
 
-/// Check if any coordinate of `shape` is occupied in `tilemap`
-pub fn check_shape_collision(shape: &Shape, tilemap: &Tilemap) -> bool {
+/// Check if any coordinate of `shape` is occupied in `grid`
+pub fn check_shape_collision(shape: &Shape, grid: &impl Grid) -> bool {
   shape.iter()
-    .any(|c| tilemap.is_occupied(c))
+    .any(|c| grid.is_occupied(c))
 }
 
 // Score and Level //
@@ -75,8 +99,7 @@ pub fn calculate_score(lines: u32, level: u32) -> Result<u32, String> {
     1 => SINGLE_LINE_MULTIPLIER,
     2 => DOUBLE_LINE_MULTIPLIER,
     3 => TRIPLE_LINE_MULTIPLIER,
-    4 => TETRIS_MULTIPLIER,
-    _ => return Err(String::from("Invalid number of lines cleared"))
+    _ => TETRIS_MULTIPLIER, // pieces taller than four tiles can clear more lines, scored as a Tetris
   };
 
   Ok(score * level)
@@ -88,11 +111,37 @@ pub fn calculate_speed_ms(level: u32) -> Result<u64, String> {
   Ok(speed)
 }
 
+/// Calculate the lines of garbage sent to an opponent for clearing `lines` at once
+pub fn calculate_attack(lines: u32) -> u32 {
+  match lines {
+    0 => 0,
+    1 => SINGLE_LINE_ATTACK,
+    2 => DOUBLE_LINE_ATTACK,
+    3 => TRIPLE_LINE_ATTACK,
+    _ => TETRIS_ATTACK, // pieces taller than four tiles can clear more lines, attacking as a Tetris
+  }
+}
+
 pub fn determine_sfx(lines: u32) -> Option<&'static str> {
   if lines > 0 && lines < MAX_LINES {
     return Some("line");
-  } else if lines == MAX_LINES {
+  } else if lines >= MAX_LINES {
     return Some("tetris");
   }
   return None;
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn attacks_by_lines_cleared() {
+    assert_eq!(calculate_attack(0), 0);
+    assert_eq!(calculate_attack(1), SINGLE_LINE_ATTACK);
+    assert_eq!(calculate_attack(2), DOUBLE_LINE_ATTACK);
+    assert_eq!(calculate_attack(3), TRIPLE_LINE_ATTACK);
+    assert_eq!(calculate_attack(4), TETRIS_ATTACK);
+    assert_eq!(calculate_attack(5), TETRIS_ATTACK);
+  }
+}
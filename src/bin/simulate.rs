@@ -0,0 +1,67 @@
+use std::env;
+use std::path::Path;
+
+use tetris::ai::evaluate::Weights;
+use tetris::bot::Difficulty;
+use tetris::piece_set::{PieceSet, use_piece_set};
+use tetris::constants::game::{DEFAULT_SIMULATION_GAMES, DEFAULT_SIMULATION_PIECES, DEFAULT_SIMULATION_SEED};
+use tetris::simulation::{Preset, Simulation};
+
+/**
+ * Benchmark the AI over seeded games without a window, printing a JSON summary
+ *
+ * simulate [--games N] [--seed N] [--pieces N] [--bot easy|normal|hard] [--preset marathon|endless|garbage]
+ *          [--weights height,holes,bumpiness,wells,lines] [--piece-set path]
+ */
+
+pub fn main() -> Result<(), String> {
+  let simulation = parse_args(env::args().skip(1))?;
+  let summary = simulation.run();
+  let json = serde_json::to_string_pretty(&summary).map_err(|error| error.to_string())?;
+  println!("{json}");
+  Ok(())
+}
+
+/// Build a simulation from the command line, using the defaults for anything not given
+fn parse_args(mut args: impl Iterator<Item=String>) -> Result<Simulation, String> {
+  let mut simulation = Simulation {
+    games: DEFAULT_SIMULATION_GAMES,
+    seed: DEFAULT_SIMULATION_SEED,
+    preset: Preset::Marathon,
+    difficulty: Difficulty::Hard,
+    weights: Weights::default(),
+    max_pieces: DEFAULT_SIMULATION_PIECES,
+  };
+
+  while let Some(arg) = args.next() {
+    let value = args.next().ok_or(format!("missing value for {arg}"))?;
+    match arg.as_str() {
+      "--games" => simulation.games = parse_number(&arg, &value)?,
+      "--seed" => simulation.seed = parse_number(&arg, &value)?,
+      "--pieces" => simulation.max_pieces = parse_number(&arg, &value)?,
+      "--bot" => simulation.difficulty = Difficulty::from_name(&value).ok_or(format!("unknown bot {value}"))?,
+      "--preset" => simulation.preset = Preset::from_name(&value).ok_or(format!("unknown preset {value}"))?,
+      "--weights" => simulation.weights = parse_weights(&value)?,
+      "--piece-set" => use_piece_set(PieceSet::load(Path::new(&value))?)?,
+      _ => return Err(format!("unknown argument {arg}")),
+    }
+  }
+
+  Ok(simulation)
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+  value.parse().map_err(|_| format!("invalid value for {arg}: {value}"))
+}
+
+/// Parse five comma separated weights, in the order of the fields of `Weights`
+fn parse_weights(value: &str) -> Result<Weights, String> {
+  let weights = value.split(',')
+    .map(|weight| parse_number("--weights", weight.trim()))
+    .collect::<Result<Vec<f32>, String>>()?;
+
+  match weights[..] {
+    [height, holes, bumpiness, wells, lines] => Ok(Weights { height, holes, bumpiness, wells, lines }),
+    _ => Err(String::from("--weights takes 5 values: height,holes,bumpiness,wells,lines")),
+  }
+}
@@ -0,0 +1,11 @@
+use std::io;
+
+use tetris::tbp::server::serve;
+
+/**
+ * Play the game's AI from any frontend that speaks the Tetris Bot Protocol over stdio
+ */
+
+pub fn main() -> Result<(), String> {
+  serve(io::stdin().lock(), io::stdout().lock()).map_err(|error| error.to_string())
+}
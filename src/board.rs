@@ -1,20 +1,23 @@
+use std::collections::VecDeque;
 use std::rc::Rc;
 use std::time::Duration;
 
-use sdl2::keyboard::Keycode;
+use serde::{Deserialize, Serialize};
 
-use crate::algorithm::{calculate_speed_ms, check_shape_collision, transform_shape};
-use crate::constants::game::{BOARD_DIMENSIONS, BOARD_POSITION, BORDER_COLOR, BORDER_MARGIN, FIRST_ROW, SPAWN_OFFSET_X, START_TETRIS_LEVEL, TILE_PIECE_MARGIN};
-use crate::constants::piece::ShapeType;
-use crate::engine::event::EventStore;
+use crate::ai::field::Field;
+use crate::algorithm::{calculate_speed_ms, check_bounds, check_shape_collision, Grid, transform_shape};
+use crate::constants::game::{BORDER_COLOR, BORDER_MARGIN, BUFFER_ROWS, GARBAGE_TILE_ID, START_TETRIS_LEVEL, TILE_PIECE_MARGIN};
+use crate::constants::piece::{Shape, ShapeType, T_SPIN_CORNERS, T_SPIN_MIN_CORNERS};
 use crate::engine::geometry::{Rec2, Vec2};
+use crate::engine::render::color::RGBA;
 use crate::engine::render::Renderer;
-use crate::engine::tile::tile::TileData;
+use crate::engine::tile::tile::{TileData, TileId};
 use crate::engine::tile::tilemap::Tilemap;
 use crate::engine::tile::tileset::Tileset;
 use crate::engine::time::{ConsumeAction, Timer};
-use crate::engine::utility::types::{Coordinate, Size2};
-use crate::piece::{erase_piece, Piece, PieceState, rotate_piece, Transform, transform_piece, write_piece};
+use crate::engine::utility::random::{random, SeededRandom};
+use crate::engine::utility::types::{Coordinate, Size, Size2};
+use crate::piece::{erase_piece, evaluate_transform, Piece, PieceState, rotate_piece, spawn_position, Transform, TransformResult, transform_piece, write_piece};
 
 /**
  * Board and piece management
@@ -24,71 +27,194 @@ use crate::piece::{erase_piece, Piece, PieceState, rotate_piece, Transform, tran
 pub struct BoardState<'a> {
   /// The current piece
   pub piece: &'a Piece,
-  /// The next piece
-  pub preview: &'a Piece,
+  /// The next piece, if there are any left
+  pub preview: Option<&'a Piece>,
   /// the current piece can spawn
   pub space: bool,
 }
 
+/// The landed tiles and pieces to come of a board, with the active piece back at its spawn
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+  cells: Vec<(Coordinate, TileId)>,
+  piece: Option<ShapeType>,
+  preview: Option<ShapeType>,
+  sequence: VecDeque<ShapeType>,
+  endless: bool,
+  random: SeededRandom,
+}
+
+/// Everything needed to resume a board where it was left
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoardSave {
+  /// visible columns and rows of the board
+  pub dimensions: Size2,
+  /// hidden rows above the visible rows
+  #[serde(default = "default_buffer_rows")]
+  pub buffer_rows: Size,
+  snapshot: BoardSnapshot,
+  /// rotation and position of the active piece
+  pose: Option<(usize, Coordinate)>,
+  /// time left until the computer drops the active piece
+  drop_remaining: Option<Duration>,
+}
+
+/// The actions requested of the board for a single update
+#[derive(Default, Clone, Copy, Debug)]
+pub struct BoardInput {
+  /// slide the piece left while held
+  pub left: bool,
+  /// slide the piece right while held
+  pub right: bool,
+  /// move the piece down while held
+  pub down: bool,
+  /// rotate the piece once
+  pub rotate: bool,
+  /// drop the piece to the floor and land it
+  pub hard_drop: bool,
+}
+
+/// A single action the board can execute on the active piece
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+  MoveLeft,
+  MoveRight,
+  Rotate,
+  SoftDrop,
+  HardDrop,
+}
+
 /// Defines the action taken during a board update
+#[derive(PartialEq, Debug)]
 pub enum BoardEvent {
   MoveLeft,
   MoveRight,
   Rotate,
   Land,
+  /// The piece dropped `distance` rows to the floor and landed
+  HardDrop { distance: u32 },
   Nothing,
 }
 
 /// The game board manages the current piece, the dropped piece, and the tilemap to render them on
+///
+/// The tilemap spans the visible board and the hidden buffer rows above it, so row `first_row` is the top visible row.
 pub struct Board {
+  /// visible columns and rows
+  dimensions: Size2,
+  /// hidden rows above the visible rows, pieces spawn in and the stack can rise into them
+  buffer_rows: Size,
   piece: Option<Piece>,
   preview: Option<Piece>,
   tilemap: Tilemap,
   drop_timeout: Timer,
   border: Size2,
+  /// pixels the board is drawn away from its position
+  offset: Vec2<i32>,
+  /// draw the active piece sliding toward the row below as the drop timeout runs
+  smooth_fall: bool,
+  /// pieces to play in order before any random pieces
+  sequence: VecDeque<ShapeType>,
+  /// draw random pieces once the sequence runs out
+  endless: bool,
+  random: SeededRandom,
 }
 
 impl Board {
-  /// construct a new board instance
-  pub fn new(tileset: Rc<Tileset>) -> Self {
-    let tilemap = Tilemap::new(Rc::clone(&tileset), BOARD_POSITION, BOARD_DIMENSIONS);
-    let (w, h) = tilemap.dimensions.destructure();
+  /// construct a new board instance of `dimensions` visible columns and rows below `buffer_rows` hidden rows, with its
+  /// visible rows at `position`
+  pub fn new(tileset: Rc<Tileset>, position: Vec2<i32>, dimensions: Size2, buffer_rows: Size) -> Self {
     let (tiles_x, tiles_y) = tileset.tile_size.destructure();
+    let matrix_position = Vec2::new(position.x, position.y - (buffer_rows * tiles_y) as i32);
+    let tilemap = Tilemap::new(Rc::clone(&tileset), matrix_position, matrix_dimensions(dimensions, buffer_rows));
+    let (w, h) = dimensions.destructure();
 
     let border = Vec2::new(w * tiles_x + BORDER_MARGIN + TILE_PIECE_MARGIN, h * tiles_y + BORDER_MARGIN + TILE_PIECE_MARGIN);
 
     let staring_fall_speed = calculate_speed_ms(START_TETRIS_LEVEL).expect("failed to calculate starting fall speed");
 
     Self {
+      dimensions,
+      buffer_rows,
       piece: None,
       preview: None,
       drop_timeout: Timer::new(Duration::from_millis(staring_fall_speed), true),
       tilemap,
       border,
+      offset: Vec2::default(),
+      smooth_fall: false,
+      sequence: VecDeque::new(),
+      endless: true,
+      random: SeededRandom::new(random(0, u64::MAX)),
     }
   }
 
   /// render the board and the current piece
   pub fn render(&self, renderer: &mut Renderer, show_tiles: bool) {
+    let position = self.position();
+
     // pause is not for cheating, don't render the board while paused
     if show_tiles {
-      // draw tiles
+      let (falling, fallen) = self.fall_offset().unwrap_or_default();
+
+      // draw tiles, leaving the buffer rows hidden
       for tile in &self.tilemap {
-        if let Some(tile) = tile {
-          let position = Vec2::new(tile.position.x, tile.position.y);
+        if let Some(tile) = tile.as_ref().filter(|tile| tile.position.y >= position.y) {
+          let mut position = tile.position + self.offset;
+          if falling.contains(&tile.position) {
+            position.y += fallen;
+          }
           renderer.draw_from_texture(&self.tilemap.tileset.texture, position, tile.src);
         }
       }
     }
 
     // draw border
-    let border_position = Vec2::new(self.tilemap.position.x - BORDER_MARGIN as i32, self.tilemap.position.y - BORDER_MARGIN as i32);
+    let border_position = Vec2::new(position.x - BORDER_MARGIN as i32, position.y - BORDER_MARGIN as i32) + self.offset;
     let rect = Rec2::new(border_position, self.border);
     renderer.draw_rect(rect, BORDER_COLOR);
   }
 
-  /// update the board and the current piece
-  pub fn update(&mut self, events: &EventStore) -> BoardEvent {
+  /// Draw an outline of `shape` at `position` on the board
+  pub fn render_outline(&self, renderer: &mut Renderer, shape: &Shape, position: &Coordinate, color: RGBA) {
+    let (tile_width, tile_height) = self.tilemap.tileset.tile_size.destructure();
+    let size = Vec2::new(tile_width - TILE_PIECE_MARGIN, tile_height - TILE_PIECE_MARGIN);
+    for coord in shape {
+      let cell = Coordinate::new(position.x + coord.x, position.y + coord.y);
+      if cell.y < self.first_row() {
+        continue; // hidden in the buffer rows
+      }
+      renderer.draw_rect(Rec2::new(self.tilemap.coord_to_worldspace(&cell) + self.offset, size), color);
+    }
+  }
+
+  /// Get where the tiles of the active piece are on screen and how many pixels they have slid toward the row below
+  ///
+  /// returns `None` unless falling is smooth and the piece has space to fall into
+  fn fall_offset(&self) -> Option<(Vec<Vec2<i32>>, i32)> {
+    let piece = self.piece.as_ref().filter(|_| self.smooth_fall)?;
+    let shape = &piece.shape_data[piece.rotation];
+    let TransformResult::Success { .. } = evaluate_transform(shape, &piece.position, Transform::Down, &self.tilemap) else {
+      return None; // resting on the stack or floor
+    };
+
+    let tiles = transform_shape(shape, &piece.position).iter().map(|cell| self.tilemap.coord_to_worldspace(cell)).collect();
+    let fallen = self.drop_timeout.progress() * self.tilemap.tileset.tile_size.y as f32;
+    Some((tiles, fallen as i32))
+  }
+
+  /// Draw the falling piece sliding between rows if `enabled`, collision is still checked a row at a time
+  pub fn set_smooth_fall(&mut self, enabled: bool) {
+    self.smooth_fall = enabled;
+  }
+
+  /// Draw the board `offset` pixels away from its position
+  pub fn set_offset(&mut self, offset: Vec2<i32>) {
+    self.offset = offset;
+  }
+
+  /// update the board and the current piece with `input`
+  pub fn update(&mut self, input: &BoardInput) -> BoardEvent {
     let mut board_event = BoardEvent::Nothing;
 
     if let Some(piece) = &mut self.piece {
@@ -103,47 +229,95 @@ impl Board {
 
       let player_can_slide = piece.player_slide_cooldown.done();
       let player_can_drop = piece.player_drop_cooldown.done();
-      let down = events.is_key_held(Keycode::S) && player_can_drop;
-      let left = events.is_key_held(Keycode::A) && player_can_slide;
-      let right = events.is_key_held(Keycode::D) && player_can_slide;
-      let rotate = events.is_key_pressed(Keycode::J);
+      let down = input.down && player_can_drop;
+      let left = input.left && player_can_slide;
+      let right = input.right && player_can_slide;
+      let rotate = input.rotate;
 
       // rotate
       if rotate {
-        rotate_piece(piece, &mut self.tilemap);
-        board_event = BoardEvent::Rotate;
+        board_event = apply_action(piece, Action::Rotate, &mut self.tilemap);
       }
 
       // slide
       if left && !right {
-        transform_piece(piece, Transform::Left, &mut self.tilemap);
+        board_event = apply_action(piece, Action::MoveLeft, &mut self.tilemap);
         piece.player_slide_cooldown.restart(); // reset the player slide cooldown
-        board_event = BoardEvent::MoveLeft;
       }
       if right && !left {
-        transform_piece(piece, Transform::Right, &mut self.tilemap);
+        board_event = apply_action(piece, Action::MoveRight, &mut self.tilemap);
         piece.player_slide_cooldown.restart(); // reset the player slide cooldown
-        board_event = BoardEvent::MoveRight;
       }
 
       // move down
       if down {
-        if transform_piece(piece, Transform::Down, &mut self.tilemap) == PieceState::Landed {
+        if apply_action(piece, Action::SoftDrop, &mut self.tilemap) == BoardEvent::Land {
           board_event = BoardEvent::Land;
         }
         self.drop_timeout.restart(); // reset the computer drop timeout
         piece.player_drop_cooldown.restart(); // reset the player drop cooldown
       }
 
+      // drop to the floor
+      if input.hard_drop {
+        board_event = apply_action(piece, Action::HardDrop, &mut self.tilemap);
+      }
+
       write_piece(piece, &mut self.tilemap); // write the new piece
     }
 
     return board_event;
   }
 
-  /// generate a random piece
-  fn get_random_piece(&self) -> Piece {
-    Piece::build(ShapeType::random(), &self.tilemap.tileset)
+  /// Immediately apply `action` to the current piece, ignoring the player cooldowns
+  pub fn execute(&mut self, action: Action) -> BoardEvent {
+    let Some(piece) = &mut self.piece else {
+      return BoardEvent::Nothing;
+    };
+
+    erase_piece(piece, &mut self.tilemap); // erase the old piece
+    let board_event = apply_action(piece, action, &mut self.tilemap);
+    if action == Action::SoftDrop {
+      self.drop_timeout.restart(); // reset the computer drop timeout
+    }
+    write_piece(piece, &mut self.tilemap); // write the new piece
+
+    board_event
+  }
+
+  /// Get the position of the top left of the visible rows on screen
+  fn position(&self) -> Vec2<i32> {
+    self.tilemap.coord_to_worldspace(&Coordinate::new(0, self.first_row()))
+  }
+
+  /// Get the next piece of the sequence, or a random piece once it runs out if the board is endless
+  fn draw_piece(&mut self) -> Option<Piece> {
+    let shape_type = match self.sequence.pop_front() {
+      Some(shape_type) => shape_type,
+      None if self.endless => {
+        let shape_types = ShapeType::all();
+        shape_types[self.random.range(0, shape_types.len())]
+      }
+      None => return None,
+    };
+    Some(Piece::build(shape_type, &self.tilemap.tileset))
+  }
+
+  /// Play `pieces` in order, replacing the active piece and preview, then random pieces if `endless`
+  ///
+  /// Call `next_piece` to spawn the first piece of the sequence.
+  pub fn set_sequence(&mut self, pieces: &[ShapeType], endless: bool) {
+    if let Some(piece) = self.piece.take() {
+      erase_piece(&piece, &mut self.tilemap);
+    }
+    self.preview = None;
+    self.sequence = pieces.iter().copied().collect();
+    self.endless = endless;
+  }
+
+  /// Check if there is a piece to spawn after the active piece
+  pub fn has_next_piece(&self) -> bool {
+    self.preview.is_some()
   }
 
   /// Set the speed the computer drops a shape
@@ -158,26 +332,225 @@ impl Board {
     !collision
   }
 
+  /// Move `piece` to its spawn, then down into view if nothing is in the way
+  ///
+  /// returns `false` if the piece overlaps the stack at its spawn
+  fn spawn(&self, piece: &mut Piece) -> bool {
+    piece.position = spawn_position(&piece.shape_type, self.dimensions.x, self.first_row());
+    let space = self.can_piece_spawn(piece);
+    if space {
+      if let TransformResult::Success { position } = evaluate_transform(&piece.shape_data[piece.rotation], &piece.position, Transform::Down, &self.tilemap) {
+        piece.position = position;
+      }
+    }
+    space
+  }
+
   /// Reset `Piece` to a new random shape and check if it can be spawned
   pub fn next_piece(&mut self) -> BoardState {
-    let mut piece = std::mem::take(&mut self.preview).unwrap_or(self.get_random_piece()); // swap the piece with the current preview
-    piece.position = Coordinate::new(SPAWN_OFFSET_X, FIRST_ROW - piece.shape_type.data().offset_y as i32);
-    let space = self.can_piece_spawn(&piece);
+    let mut piece = std::mem::take(&mut self.preview)
+      .or_else(|| self.draw_piece())
+      .expect("failed to draw piece"); // swap the piece with the current preview
+    let space = self.spawn(&mut piece);
 
     write_piece(&piece, &mut self.tilemap); // write the new piece
     self.piece = Some(piece); // get a new piece
-    let mut preview = self.get_random_piece(); // create a new preview
-    preview.position = preview.shape_type.data().preview_offset;
-    self.preview = Some(preview); // create a new preview
+    self.preview = self.draw_piece().map(into_preview); // create a new preview
     self.drop_timeout.restart(); // ensure the drop timeout is 0
 
     BoardState {
       piece: self.piece.as_ref().expect("failed to retrieve piece"),
-      preview: self.preview.as_ref().expect("failed to retrieve preview"),
+      preview: self.preview.as_ref(),
       space,
     }
   }
 
+  /// Get the visible columns and rows of the board
+  pub fn dimensions(&self) -> Size2 {
+    self.dimensions
+  }
+
+  /// Get the hidden rows above the visible rows
+  pub fn buffer_rows(&self) -> Size {
+    self.buffer_rows
+  }
+
+  /// Get the first visible row of the matrix
+  pub fn first_row(&self) -> i32 {
+    self.buffer_rows as i32
+  }
+
+  /// Get the columns and rows of the board, including the hidden buffer rows
+  pub fn matrix_dimensions(&self) -> Size2 {
+    self.tilemap.dimensions
+  }
+
+  /// Get the tileset the board is drawn with
+  pub fn tileset(&self) -> &Tileset {
+    &self.tilemap.tileset
+  }
+
+  /// Get the active piece
+  pub fn piece(&self) -> Option<&Piece> {
+    self.piece.as_ref()
+  }
+
+  /// Get the next piece
+  pub fn preview(&self) -> Option<&Piece> {
+    self.preview.as_ref()
+  }
+
+  /// Get the cells occupied by landed tiles
+  pub fn field(&self) -> Field {
+    let mut field = Field::from_grid(&self.tilemap);
+
+    // the active piece is written to the tilemap, so remove it
+    if let Some(piece) = &self.piece {
+      for coord in &piece.shape_data[piece.rotation] {
+        field.clear(&Coordinate::new(piece.position.x + coord.x, piece.position.y + coord.y));
+      }
+    }
+
+    field
+  }
+
+  /// Get the landed tiles and the tile filling each
+  pub fn cells(&self) -> Vec<(Coordinate, TileId)> {
+    let field = self.field();
+    let (width, height) = self.tilemap.dimensions.destructure();
+    (0..height as i32)
+      .flat_map(|y| (0..width as i32).map(move |x| Coordinate::new(x, y)))
+      .filter(|coordinate| field.is_occupied(coordinate))
+      .filter_map(|coordinate| self.tilemap.get_at_coord(&coordinate).map(|tile| (coordinate, tile.id)))
+      .collect()
+  }
+
+  /// Fill `coordinate` with the tile `tile_id`
+  pub fn fill(&mut self, coordinate: &Coordinate, tile_id: TileId) {
+    let data = self.tilemap.tileset.get_tiledata(tile_id).expect("failed to get tile data");
+    self.tilemap.set_tile_at_coord(coordinate, data);
+  }
+
+  /// Remove the tile at `coordinate`
+  pub fn erase(&mut self, coordinate: &Coordinate) {
+    self.tilemap.clear_tile_at_coord(coordinate);
+  }
+
+  /// Get the area of the cell at `coordinate` on screen
+  pub fn cell_area(&self, coordinate: &Coordinate) -> Rec2<i32, Size> {
+    Rec2::new(self.tilemap.coord_to_worldspace(coordinate), self.tilemap.tileset.tile_size)
+  }
+
+  /// Get the coordinate of the cell at `position` on screen, if it is on the visible rows of the board
+  pub fn coordinate_at(&self, position: Vec2<i32>) -> Option<Coordinate> {
+    self.tilemap.worldspace_to_coord(position).filter(|coordinate| coordinate.y >= self.first_row())
+  }
+
+  /// Check if the active piece is wholly within the hidden buffer rows
+  pub fn is_locked_out(&self) -> bool {
+    self.piece.as_ref().is_some_and(|piece| {
+      piece.shape_data[piece.rotation].iter().all(|coord| piece.position.y + coord.y < self.first_row())
+    })
+  }
+
+  /// Check if the active piece is a T that spun into a slot with at least 3 of its corners filled
+  pub fn is_t_spin(&self) -> bool {
+    let Some(piece) = self.piece.as_ref().filter(|piece| piece.shape_type == ShapeType::T && piece.spun) else {
+      return false;
+    };
+
+    let field = self.field();
+    let corners = T_SPIN_CORNERS.iter()
+      .map(|corner| Coordinate::new(piece.position.x + corner.x, piece.position.y + corner.y))
+      .filter(|corner| corner.y >= 0 && (!field.is_bound(corner) || field.is_occupied(corner)))
+      .count();
+    corners >= T_SPIN_MIN_CORNERS
+  }
+
+  /// Take a snapshot of the landed tiles and the pieces to come
+  pub fn snapshot(&self) -> BoardSnapshot {
+    BoardSnapshot {
+      cells: self.cells(),
+      piece: self.piece.as_ref().map(|piece| piece.shape_type),
+      preview: self.preview.as_ref().map(|preview| preview.shape_type),
+      sequence: self.sequence.clone(),
+      endless: self.endless,
+      random: self.random.clone(),
+    }
+  }
+
+  /// Return to `snapshot`, spawning its active piece
+  pub fn restore(&mut self, snapshot: &BoardSnapshot) {
+    self.clear();
+    for (coordinate, tile_id) in &snapshot.cells {
+      self.fill(coordinate, *tile_id);
+    }
+
+    self.piece = snapshot.piece.map(|shape_type| {
+      let mut piece = Piece::build(shape_type, &self.tilemap.tileset);
+      self.spawn(&mut piece);
+      write_piece(&piece, &mut self.tilemap);
+      piece
+    });
+    self.preview = snapshot.preview.map(|shape_type| into_preview(Piece::build(shape_type, &self.tilemap.tileset)));
+    self.sequence = snapshot.sequence.clone();
+    self.endless = snapshot.endless;
+    self.random = snapshot.random.clone();
+    self.drop_timeout.restart();
+  }
+
+  /// Save the board with the active piece where it is
+  pub fn save(&self) -> BoardSave {
+    BoardSave {
+      dimensions: self.dimensions,
+      buffer_rows: self.buffer_rows,
+      snapshot: self.snapshot(),
+      pose: self.piece.as_ref().map(|piece| (piece.rotation, piece.position)),
+      drop_remaining: self.drop_timeout.remaining(),
+    }
+  }
+
+  /// Resume the board from `save`
+  pub fn load(&mut self, save: &BoardSave) -> Result<(), String> {
+    if save.dimensions != self.dimensions {
+      return Err(format!("saved board is {}x{}, not {}x{}", save.dimensions.x, save.dimensions.y, self.dimensions.x, self.dimensions.y));
+    }
+    if save.buffer_rows != self.buffer_rows {
+      return Err(format!("saved board has {} buffer rows, not {}", save.buffer_rows, self.buffer_rows));
+    }
+
+    let snapshot = &save.snapshot;
+    if let Some((coordinate, _)) = snapshot.cells.iter().find(|(coordinate, _)| !self.tilemap.is_bound(coordinate)) {
+      return Err(format!("saved tile at {}, {} is off the board", coordinate.x, coordinate.y));
+    }
+    if let Some((_, tile_id)) = snapshot.cells.iter().find(|(_, tile_id)| self.tilemap.tileset.get_tiledata(*tile_id).is_none()) {
+      return Err(format!("unknown saved tile {tile_id}"));
+    }
+
+    self.restore(snapshot);
+    if let (Some(piece), Some((rotation, position))) = (&mut self.piece, save.pose) {
+      if rotation >= piece.shape_data.len() {
+        return Err(format!("unknown saved rotation {rotation}"));
+      }
+      erase_piece(piece, &mut self.tilemap);
+      let shape = transform_shape(&piece.shape_data[rotation], &position);
+      if !check_bounds(&shape, &self.tilemap) || check_shape_collision(&shape, &self.tilemap) {
+        write_piece(piece, &mut self.tilemap);
+        return Err(format!("saved piece at {}, {} is off the board or overlaps the stack", position.x, position.y));
+      }
+      piece.rotation = rotation;
+      piece.position = position;
+      write_piece(piece, &mut self.tilemap);
+    }
+    self.drop_timeout.resume(save.drop_remaining);
+    Ok(())
+  }
+
+  /// Remove every landed tile
+  pub fn clear(&mut self) {
+    self.tilemap.clear_tiles();
+  }
+
   /// Set the `Piece` to `None`
   pub fn kill_piece(&mut self) {
     self.piece = None;
@@ -185,16 +558,17 @@ impl Board {
 
   /// Transform tiles on lines above `line` by {0, 1}
   pub fn move_lines_down(&mut self, line: usize) -> Result<(), String> {
-    if line > BOARD_DIMENSIONS.y as usize {
+    let (width, height) = self.tilemap.dimensions.destructure();
+    if line > height as usize {
       return Err(String::from("line move out of bounds"));
     }
-    if line == BOARD_DIMENSIONS.y as usize {
+    if line == height as usize {
       return Err(String::from("cannot move last line"));
     }
 
     // loop the lines from the line to the top
     for y in (0..line).rev() {
-      for x in 0..BOARD_DIMENSIONS.x {
+      for x in 0..width {
         // get tile
         let coord = Coordinate::new(x as i32, y as i32);
         let tile = self.tilemap.get_at_coord(&coord);
@@ -216,9 +590,10 @@ impl Board {
   /// Get lines containing only `Some` tiles
   pub fn get_full_lines(&self) -> Vec<usize> {
     let mut full_lines = Vec::new();
-    for y in 0..BOARD_DIMENSIONS.y {
+    let (width, height) = self.tilemap.dimensions.destructure();
+    for y in 0..height {
       let mut line_full = true;
-      for x in 0..BOARD_DIMENSIONS.x {
+      for x in 0..width {
         let coord = Coordinate::new(x as i32, y as i32);
         if !self.tilemap.is_occupied(&coord) {
           line_full = false;
@@ -235,15 +610,105 @@ impl Board {
 
   /// Set all tiles in a line to `None`
   pub fn clear_line(&mut self, line: usize) -> Result<(), String> {
-    if line >= BOARD_DIMENSIONS.y as usize {
+    let (width, height) = self.tilemap.dimensions.destructure();
+    if line >= height as usize {
       return Err(String::from("line clear out of bounds"));
     }
 
-    for x in 0..BOARD_DIMENSIONS.x {
+    for x in 0..width {
       let coord = Coordinate::new(x as i32, line as i32);
       self.tilemap.clear_tile_at_coord(&coord);
     }
 
     Ok(())
   }
-}
\ No newline at end of file
+
+  /// Remove the tile at `coordinate`, returning the id of the tile removed
+  pub fn clear_tile(&mut self, coordinate: &Coordinate) -> Option<TileId> {
+    let tile_id = self.tilemap.get_at_coord(coordinate).map(|tile| tile.id);
+    self.tilemap.clear_tile_at_coord(coordinate);
+    tile_id
+  }
+
+  /// Push the tiles up by `lines` and fill the space below with garbage, leaving a gap at `hole`
+  ///
+  /// returns `false` if any tiles were pushed off the top of the buffer rows
+  pub fn add_garbage(&mut self, lines: u32, hole: i32) -> bool {
+    let (width, height) = self.tilemap.dimensions.destructure();
+    let lines = lines.min(height) as i32;
+    let mut overflow = false;
+
+    // move every line up
+    for y in 0..height as i32 {
+      for x in 0..width as i32 {
+        let coord = Coordinate::new(x, y);
+        if let Some(tile) = self.tilemap.get_at_coord(&coord) {
+          let data = TileData { id: tile.id, src: tile.src };
+          if y < lines {
+            overflow = true; // tile is pushed off the board
+          } else {
+            self.tilemap.set_tile_at_coord(&Coordinate::new(x, y - lines), data);
+          }
+        }
+        self.tilemap.clear_tile_at_coord(&coord);
+      }
+    }
+
+    // fill the bottom lines
+    let garbage = self.tilemap.tileset.get_tiledata(GARBAGE_TILE_ID).expect("failed to get garbage tile data");
+    for y in height as i32 - lines..height as i32 {
+      for x in (0..width as i32).filter(|x| *x != hole) {
+        self.tilemap.set_tile_at_coord(&Coordinate::new(x, y), garbage);
+      }
+    }
+
+    !overflow
+  }
+}
+
+/// Get the columns and rows of a board of `dimensions` visible columns and rows below `buffer_rows` hidden rows
+pub fn matrix_dimensions(dimensions: Size2, buffer_rows: Size) -> Size2 {
+  Vec2::new(dimensions.x, dimensions.y + buffer_rows)
+}
+
+fn default_buffer_rows() -> Size {
+  BUFFER_ROWS
+}
+
+/// Move `piece` to where it is drawn in the preview
+fn into_preview(mut piece: Piece) -> Piece {
+  piece.position = piece.shape_type.data().preview_offset;
+  piece
+}
+
+/// Apply `action` to `piece` on `tilemap`
+fn apply_action(piece: &mut Piece, action: Action, tilemap: &mut Tilemap) -> BoardEvent {
+  match action {
+    Action::MoveLeft => {
+      transform_piece(piece, Transform::Left, tilemap);
+      BoardEvent::MoveLeft
+    }
+    Action::MoveRight => {
+      transform_piece(piece, Transform::Right, tilemap);
+      BoardEvent::MoveRight
+    }
+    Action::Rotate => {
+      rotate_piece(piece, tilemap);
+      BoardEvent::Rotate
+    }
+    Action::SoftDrop => {
+      if transform_piece(piece, Transform::Down, tilemap) == PieceState::Landed {
+        BoardEvent::Land
+      } else {
+        BoardEvent::Nothing
+      }
+    }
+    Action::HardDrop => {
+      let mut distance = 0;
+      while transform_piece(piece, Transform::Down, tilemap) != PieceState::Landed {
+        distance += 1;
+      }
+      BoardEvent::HardDrop { distance }
+    }
+  }
+}
@@ -0,0 +1,210 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::ai::evaluate::Weights;
+use crate::ai::best_placement;
+use crate::ai::search::{find_path, find_placements, Placement, Pose};
+use crate::board::{Action, Board};
+use crate::constants::game::{BOT_ACTION_INTERVAL, EASY_MISTAKE_RATE, HARD_MISTAKE_RATE, NORMAL_MISTAKE_RATE, SPAWN_COOLDOWN};
+use crate::engine::utility::random::random;
+
+/**
+ * A computer opponent that plays its own board
+ */
+
+/// How well the bot plays
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty { Easy, Normal, Hard }
+
+impl Difficulty {
+  pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+  /// Get the display name of the difficulty
+  pub fn name(&self) -> &'static str {
+    match self {
+      Difficulty::Easy => "EASY",
+      Difficulty::Normal => "NORMAL",
+      Difficulty::Hard => "HARD",
+    }
+  }
+
+  /// Get the difficulty named `name`, ignoring case
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+  }
+
+  /// Chance the bot places a piece at random instead of the best placement
+  pub fn mistake_rate(&self) -> f32 {
+    match self {
+      Difficulty::Easy => EASY_MISTAKE_RATE,
+      Difficulty::Normal => NORMAL_MISTAKE_RATE,
+      Difficulty::Hard => HARD_MISTAKE_RATE,
+    }
+  }
+
+  /// Should the bot consider the next piece when placing the current piece
+  pub fn lookahead(&self) -> bool {
+    *self == Difficulty::Hard
+  }
+}
+
+/// Configures how a bot plays
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BotSettings {
+  /// The most pieces the bot will place each second
+  pub pieces_per_second: f32,
+  pub difficulty: Difficulty,
+}
+
+/// Feeds the actions of a placement to a board at a steady pace
+pub struct Pilot {
+  /// where the active piece will be placed
+  target: Option<Pose>,
+  /// actions remaining to reach the target, and the pose each leaves the piece in
+  path: VecDeque<(Action, Pose)>,
+  /// the pose the piece should be in before the next action
+  expected: Option<Pose>,
+  next_action_at: Instant,
+  drop_at: Instant,
+}
+
+impl Pilot {
+  /// Instantiate a new pilot without a target
+  pub fn new() -> Self {
+    let now = Instant::now();
+    Self {
+      target: None,
+      path: VecDeque::new(),
+      expected: None,
+      next_action_at: now,
+      drop_at: now,
+    }
+  }
+
+  /// Forget the target, ready for the next piece
+  pub fn reset(&mut self) {
+    self.target = None;
+    self.path.clear();
+    self.expected = None;
+  }
+
+  /// Get where the active piece will be placed
+  pub fn target(&self) -> Option<Pose> {
+    self.target
+  }
+
+  /// Check if the piece at `pose` is where the pilot left it
+  pub fn on_course(&self, pose: Pose) -> bool {
+    self.expected == Some(pose)
+  }
+
+  /// Queue the actions to reach `placement` from `pose`, locking no sooner than `drop_at`
+  ///
+  /// Without a placement the piece is dropped in place.
+  pub fn follow(&mut self, placement: Option<Placement>, pose: Pose, drop_at: Instant) {
+    let placement = placement.unwrap_or(Placement { pose, path: vec![(Action::HardDrop, pose)] });
+    self.target = Some(placement.pose);
+    self.path = VecDeque::from(placement.path);
+    self.expected = Some(pose);
+    self.drop_at = drop_at;
+  }
+
+  /// Find a new path to the target for the active piece on `board`
+  ///
+  /// returns `false` if the target can no longer be reached
+  pub fn reroute(&mut self, board: &Board) -> bool {
+    let (Some(piece), Some(target)) = (board.piece(), self.target) else {
+      return false;
+    };
+
+    let pose = Pose::from(piece);
+    match find_path(&board.field(), piece.shape_type.data(), pose, target) {
+      Some(placement) => {
+        self.follow(Some(placement), pose, self.drop_at);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Take the next action towards the target if enough time has passed
+  pub fn next_action(&mut self) -> Option<Action> {
+    let now = Instant::now();
+    if now < self.next_action_at {
+      return None;
+    }
+
+    let (action, next_pose) = *self.path.front()?;
+    if action == Action::HardDrop && now < self.drop_at {
+      return None;
+    }
+
+    self.path.pop_front();
+    self.expected = Some(next_pose);
+    self.next_action_at = now + BOT_ACTION_INTERVAL;
+    Some(action)
+  }
+}
+
+/// Produces actions for a board in place of a keyboard
+pub struct Bot {
+  settings: BotSettings,
+  weights: Weights,
+  pilot: Pilot,
+}
+
+impl Bot {
+  /// Instantiate a new bot with `settings`
+  pub fn new(settings: BotSettings) -> Self {
+    Self {
+      settings,
+      weights: Weights::default(),
+      pilot: Pilot::new(),
+    }
+  }
+
+  /// Decide the action for `board` this frame, if any
+  pub fn poll(&mut self, board: &Board) -> Option<Action> {
+    let Some(piece) = board.piece() else {
+      self.pilot.reset(); // the piece has landed, plan the next one
+      return None;
+    };
+
+    let pose = Pose::from(piece);
+    if self.pilot.target().is_none() {
+      self.plan(board, pose);
+    } else if !self.pilot.on_course(pose) && !self.pilot.reroute(board) {
+      self.plan(board, pose); // gravity moved the piece somewhere the target can't be reached from
+    }
+
+    self.pilot.next_action()
+  }
+
+  /// Choose a placement for the active piece at `pose`
+  fn plan(&mut self, board: &Board, pose: Pose) {
+    let piece = board.piece().expect("failed to retrieve piece");
+    let next = board.preview()
+      .filter(|_| self.settings.difficulty.lookahead())
+      .map(|preview| preview.shape_type);
+
+    let field = board.field();
+    let placement = if random(0.0, 1.0) < self.settings.difficulty.mistake_rate() {
+      let mut placements = find_placements(&field, piece.shape_type.data(), pose);
+      (!placements.is_empty()).then(|| placements.swap_remove(random(0, placements.len())))
+    } else {
+      best_placement(&field, piece.shape_type, pose, next, board.first_row(), &self.weights)
+    };
+
+    self.pilot.follow(placement, pose, drop_time(self.settings.pieces_per_second));
+  }
+}
+
+/// Get when a piece planned now may be dropped to place no more than `pieces_per_second`
+pub fn drop_time(pieces_per_second: f32) -> Instant {
+  // account for the time spent waiting for the piece to spawn
+  let per_piece = Duration::from_secs_f32(1.0 / pieces_per_second);
+  Instant::now() + per_piece.saturating_sub(SPAWN_COOLDOWN)
+}
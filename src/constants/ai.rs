@@ -0,0 +1,9 @@
+/**
+ * Default weights for evaluating fields
+ */
+
+pub const HEIGHT_WEIGHT: f32 = -0.51;
+pub const HOLES_WEIGHT: f32 = -0.36;
+pub const BUMPINESS_WEIGHT: f32 = -0.18;
+pub const WELLS_WEIGHT: f32 = -0.08;
+pub const LINES_WEIGHT: f32 = 0.76;
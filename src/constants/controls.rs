@@ -0,0 +1,54 @@
+use sdl2::keyboard::Keycode;
+
+use crate::constants::piece::ShapeType;
+use crate::controls::Controls;
+
+/**
+ * Key bindings for each player
+ */
+
+pub const PLAYER_ONE_CONTROLS: Controls = Controls {
+  left: Keycode::A,
+  right: Keycode::D,
+  down: Keycode::S,
+  rotate: Keycode::J,
+  hard_drop: Keycode::W,
+};
+
+pub const PLAYER_TWO_CONTROLS: Controls = Controls {
+  left: Keycode::Left,
+  right: Keycode::Right,
+  down: Keycode::Down,
+  rotate: Keycode::Up,
+  hard_drop: Keycode::RShift,
+};
+
+// Practice //
+
+pub const UNDO_KEY: Keycode = Keycode::Backspace;
+pub const RETRY_KEY: Keycode = Keycode::R;
+
+// Editor //
+
+pub const EDITOR_UP: Keycode = Keycode::Up;
+pub const EDITOR_DOWN: Keycode = Keycode::Down;
+pub const EDITOR_LEFT: Keycode = Keycode::Left;
+pub const EDITOR_RIGHT: Keycode = Keycode::Right;
+pub const EDITOR_PAINT: Keycode = Keycode::Space;
+pub const EDITOR_ERASE: Keycode = Keycode::Delete;
+pub const EDITOR_BRUSH: Keycode = Keycode::Tab;
+pub const EDITOR_UNQUEUE: Keycode = Keycode::Backspace;
+pub const EDITOR_CLEAR: Keycode = Keycode::C;
+pub const EDITOR_PLAY: Keycode = Keycode::Return;
+pub const EDITOR_SAVE: Keycode = Keycode::F5;
+
+/// Keys that add each piece to the end of the queue
+pub const EDITOR_QUEUE_KEYS: [(Keycode, ShapeType); 7] = [
+  (Keycode::I, ShapeType::I),
+  (Keycode::J, ShapeType::J),
+  (Keycode::L, ShapeType::L),
+  (Keycode::O, ShapeType::O),
+  (Keycode::S, ShapeType::S),
+  (Keycode::T, ShapeType::T),
+  (Keycode::Z, ShapeType::Z),
+];
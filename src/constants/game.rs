@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use crate::engine::geometry::{Rec2, Vec2};
-use crate::engine::render::color::{color, RGBA};
+use crate::engine::render::color::{color, RGBA, U8MAX, UNTINTED};
+use crate::engine::render::particle::EmitterSettings;
+use crate::engine::tile::tile::TileId;
 use crate::engine::utility::types::{Size, Size2};
 
 /**
@@ -10,6 +12,9 @@ use crate::engine::utility::types::{Size, Size2};
 
 // Displays //
 
+pub const STANDARD_LAYOUT: &str = "STANDARD";
+pub const TYPEFACE: &str = "typeface"; // labels are drawn in the standard typeface unless the layout chooses another
+
 pub const STATISTICS_BORDER: Rec2<i32, Size> = Rec2::new(Vec2::new(99, 8), Vec2::new(83u32, 31u32));
 pub const SCORE_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 10);
 pub const LINES_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 20);
@@ -17,13 +22,107 @@ pub const LEVEL_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 30);
 
 pub const PREVIEW_BORDER: Rec2<i32, Size> = Rec2::new(Vec2::new(99, 48), Vec2::new(83u32, 33u32));
 pub const PREVIEW_POSITION: Vec2<i32> = Vec2::new(142, 49);
-pub const PREVIEW_DIMENSIONS: Vec2<Size> = Vec2::new(4, 4);
+pub const PREVIEW_DIMENSIONS: Vec2<Size> = Vec2::new(5, 4);
 pub const NEXT_TEXT_POSITION: Vec2<i32> = Vec2::new(108, 61);
 
 pub const STATE_TEXT_POSITION: Vec2<i32> = Vec2::new(101, 90);
 pub const GAME_WON_TEXT: &str = "Look ma, I won!";
 pub const GAME_OVER_TEXT: &str = "GAME OVER";
 pub const GAME_PAUSED_TEXT: &str = "PAUSED";
+pub const PLAYER_WON_TEXT: &str = "WINNER!";
+pub const TOP_OUT_TEXT_POSITION: Vec2<i32> = Vec2::new(101, 100);
+pub const BLOCK_OUT_TEXT: &str = "BLOCK OUT";
+pub const LOCK_OUT_TEXT: &str = "LOCK OUT";
+pub const GARBAGE_OUT_TEXT: &str = "BURIED";
+pub const OUT_OF_PIECES_TEXT: &str = "OUT OF PIECES";
+
+pub const GARBAGE_METER_POSITION: Vec2<i32> = Vec2::new(93, 10);
+pub const GARBAGE_METER_WIDTH: Size = 4;
+pub const GARBAGE_METER_COLOR: RGBA = color::TEXT;
+
+pub const HINT_COLOR: RGBA = color::OVERLAY_0;
+pub const DRILL_TARGET_COLOR: RGBA = color::TEXT;
+
+pub const OBJECTIVE_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 145);
+pub const FINESSE_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 155);
+pub const FAULT_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 165);
+pub const FAULT_TEXT: &str = "FINESSE FAULT";
+pub const FAULT_COLOR: RGBA = color::RED;
+
+// Transitions //
+
+pub const LOCK_BOUNCE_DEPTH: f32 = 2.0; // pixels the board dips when a piece locks
+pub const LOCK_BOUNCE_DIP: Duration = Duration::from_millis(50);
+pub const LOCK_BOUNCE_SETTLE: Duration = Duration::from_millis(250);
+pub const SCORE_ROLL_DURATION: Duration = Duration::from_millis(500);
+pub const MENU_SLIDE_DISTANCE: i32 = 40; // pixels menus slide in from the left
+pub const MENU_SLIDE_DURATION: Duration = Duration::from_millis(250);
+
+// Screen Shake //
+
+pub const SHAKE_PER_ROW: f32 = 0.15; // pixels of shake for each row a piece hard drops
+pub const SHAKE_PER_LINE: f32 = 1.0; // pixels of shake for each line of a multi-line clear
+pub const MAX_SHAKE: f32 = 4.0;
+pub const SHAKE_DURATION: Duration = Duration::from_millis(300);
+
+// Title //
+
+pub const TITLE_TEXT_POSITION: Vec2<i32> = Vec2::new(78, 50);
+pub const MENU_POSITION: Vec2<i32> = Vec2::new(66, 80);
+pub const MENU_SPACING: i32 = 10;
+pub const CONTINUE_TEXT: &str = "CONTINUE";
+pub const MARATHON_TEXT: &str = "MARATHON";
+pub const VERSUS_TEXT: &str = "VERSUS";
+pub const VERSUS_BOT_TEXT: &str = "VS BOT";
+pub const DRILL_TEXT: &str = "DRILL";
+pub const PUZZLE_TEXT: &str = "PUZZLE";
+pub const PRACTICE_TEXT: &str = "PRACTICE";
+pub const WIDTH_TEXT: &str = "WIDTH";
+pub const HEIGHT_TEXT: &str = "HEIGHT";
+
+// Puzzle //
+
+pub const PUZZLES_PATH: &str = "asset/puzzles.json";
+pub const PUZZLE_SOLVED_TEXT: &str = "SOLVED!";
+pub const PUZZLE_FAILED_TEXT: &str = "FAILED";
+pub const SOLVED_MARKER: &str = " *"; // follows the names of solved puzzles
+
+// Editor //
+
+pub const EDITOR_TEXT: &str = "EDITOR";
+pub const BRUSH_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 10);
+pub const BRUSH_TILE_POSITION: Vec2<i32> = Vec2::new(130, 9);
+pub const QUEUE_TEXT_POSITION: Vec2<i32> = Vec2::new(102, 25);
+pub const QUEUE_PIECES_POSITION: Vec2<i32> = Vec2::new(102, 35);
+pub const QUEUE_SHOWN: usize = 12; // pieces listed before the rest are cut off
+pub const EDITOR_HELP_POSITION: Vec2<i32> = Vec2::new(102, 55);
+pub const EDITOR_HELP: [&str; 9] = ["ARROWS MOVE", "SPACE PAINT", "DEL ERASE", "TAB BRUSH", "IJLOSTZ QUEUE", "BKSP UNQUEUE", "C CLEAR", "ENTER PLAY", "F5 SAVE"];
+pub const EDITOR_STATUS_POSITION: Vec2<i32> = Vec2::new(102, 155);
+pub const EDITOR_SAVED_TEXT: &str = "SAVED";
+pub const EDITOR_CURSOR_COLOR: RGBA = color::TEXT;
+
+// Pause //
+
+pub const PAUSE_MENU_POSITION: Vec2<i32> = Vec2::new(101, 105);
+pub const PAUSE_BLUR_RADIUS: i32 = 1; // pixels the game behind the pause menu is blurred by
+pub const PAUSE_PANEL: Rec2<i32, Size> = Rec2::new(Vec2::new(98, 102), Vec2::new(85u32, 66u32)); // fits the six pause options
+pub const PAUSE_PANEL_RADIUS: Size = 3;
+pub const PAUSE_PANEL_COLOR: RGBA = RGBA::new(24, 24, 37, 224); // mostly opaque mantle, hiding the HUD behind the menu
+pub const RESUME_TEXT: &str = "RESUME";
+pub const HINT_TEXT: &str = "HINT";
+pub const SHAKE_TEXT: &str = "SHAKE";
+pub const SMOOTH_TEXT: &str = "SMOOTH";
+pub const COPY_FUMEN_TEXT: &str = "COPY FUMEN";
+pub const FUMEN_COPIED_TEXT: &str = "COPIED";
+pub const QUIT_TEXT: &str = "QUIT";
+pub const TOGGLE_VALUES: [&str; 2] = ["OFF", "ON"];
+
+// Bot Setup //
+
+pub const BOT_MENU_POSITION: Vec2<i32> = Vec2::new(40, 80);
+pub const BOT_DIFFICULTY_TEXT: &str = "LEVEL";
+pub const BOT_SPEED_TEXT: &str = "SPEED";
+pub const START_TEXT: &str = "START";
 
 // Sound //
 
@@ -38,19 +137,89 @@ pub const TILE_PIECE_MARGIN: Size = 1; // margin between pieces in the tileset
 pub const BORDER_MARGIN: Size = 2;
 pub const BORDER_COLOR: RGBA = color::SURFACE_0;
 pub const BOARD_POSITION: Vec2<i32> = Vec2::new(10, 10);
-pub const BOARD_DIMENSIONS: Size2 = Vec2::new(10, 20);
-
-pub const FIRST_ROW: i32 = 0;
-pub const SPAWN_OFFSET_X: i32 = 4; // center the piece on the board.rs
+pub const BOARD_DIMENSIONS: Size2 = Vec2::new(10, 20); // visible rows of the standard board, the HUD is placed for it
+pub const MIN_BOARD_DIMENSIONS: Size2 = Vec2::new(4, 10);
+pub const MAX_BOARD_DIMENSIONS: Size2 = Vec2::new(20, 40);
+pub const BUFFER_ROWS: Size = 20; // hidden rows above the visible rows of a standard board, pieces spawn in and the stack can rise into them
+pub const MATRIX_DIMENSIONS: Size2 = Vec2::new(BOARD_DIMENSIONS.x, BOARD_DIMENSIONS.y + BUFFER_ROWS);
+pub const GARBAGE_TILE_ID: TileId = 8;
+
+pub const FIRST_ROW: i32 = BUFFER_ROWS as i32; // first visible row of the matrix of a standard board
+
+// Pieces //
+
+pub const STANDARD_PIECE_SET: &str = "STANDARD";
+
+// Line Clears //
+
+pub const WIPE_DURATION: Duration = Duration::from_millis(400);
+pub const DISSOLVE_DURATION: Duration = Duration::from_millis(500);
+pub const FLASH_DURATION: Duration = Duration::from_millis(700); // Tetris clears linger a little longer
+pub const FLASH_INTERVAL: Duration = Duration::from_millis(100); // time the rows are shown or hidden for each blink
+pub const FLASH_COLOR: RGBA = color::TEXT;
+
+// Particles //
+
+pub const PARTICLE_FADE: (RGBA, RGBA) = (UNTINTED, RGBA::new(U8MAX, U8MAX, U8MAX, 0));
+pub const LINE_CLEAR_PARTICLES: EmitterSettings = EmitterSettings {
+  burst: 4,
+  rate: 0.0,
+  duration: Duration::ZERO,
+  lifetime: Duration::from_millis(600),
+  velocity: (Vec2::new(-40.0, -80.0), Vec2::new(40.0, -20.0)),
+  gravity: 240.0,
+  colors: PARTICLE_FADE,
+  size: 3,
+};
+pub const HARD_DROP_PARTICLES: EmitterSettings = EmitterSettings {
+  burst: 3,
+  rate: 0.0,
+  duration: Duration::ZERO,
+  lifetime: Duration::from_millis(300),
+  velocity: (Vec2::new(-4.0, -30.0), Vec2::new(4.0, -10.0)),
+  gravity: 0.0,
+  colors: PARTICLE_FADE,
+  size: 2,
+};
+pub const LEVEL_UP_PARTICLES: EmitterSettings = EmitterSettings {
+  burst: 0,
+  rate: 12.0, // for each tile, so every piece of the set showers together
+  duration: Duration::from_millis(1_000),
+  lifetime: Duration::from_millis(1_200),
+  velocity: (Vec2::new(-20.0, 10.0), Vec2::new(20.0, 40.0)),
+  gravity: 40.0,
+  colors: PARTICLE_FADE,
+  size: 3,
+};
 
 // Cooldowns //
 
 pub const SPAWN_COOLDOWN: Duration = Duration::from_millis(300);
-pub const CLEAR_COOLDOWN: Duration = Duration::from_millis(1_000);
 pub const FALL_COOLDOWN: Duration = Duration::from_millis(1_000);
 pub const PLAYER_DROP_COOLDOWN: Duration = Duration::from_millis(40);
 pub const PLAYER_SLIDE_COOLDOWN: Duration = Duration::from_millis(100);
 
+pub const FAULT_WARNING_DURATION: Duration = Duration::from_millis(800);
+
+pub const BOT_ACTION_INTERVAL: Duration = Duration::from_millis(50); // time between each bot action
+
+// Bot //
+
+pub const BOT_SPEEDS: [(&str, f32); 5] = [("0.5 PPS", 0.5), ("1 PPS", 1.0), ("1.5 PPS", 1.5), ("2 PPS", 2.0), ("3 PPS", 3.0)];
+pub const DEFAULT_BOT_SPEED: usize = 1;
+pub const DEFAULT_BOT_DIFFICULTY: usize = 1;
+
+pub const EASY_MISTAKE_RATE: f32 = 0.35;
+pub const NORMAL_MISTAKE_RATE: f32 = 0.1;
+pub const HARD_MISTAKE_RATE: f32 = 0.0;
+
+// Simulation //
+
+pub const DEFAULT_SIMULATION_GAMES: u32 = 10;
+pub const DEFAULT_SIMULATION_SEED: u64 = 0;
+pub const DEFAULT_SIMULATION_PIECES: u32 = 500; // keep games that never top out finite
+pub const SIMULATION_GARBAGE_INTERVAL: u32 = 5; // pieces between each line of garbage in the garbage preset
+
 // Multipliers //
 
 pub const LEVEL_SPEED_MULTIPLIER: f32 = 0.8;
@@ -59,6 +228,14 @@ pub const DOUBLE_LINE_MULTIPLIER: u32 = 100;
 pub const TRIPLE_LINE_MULTIPLIER: u32 = 300;
 pub const TETRIS_MULTIPLIER: u32 = 1200;
 
+// Attack //
+
+pub const SINGLE_LINE_ATTACK: u32 = 0;
+pub const DOUBLE_LINE_ATTACK: u32 = 1;
+pub const TRIPLE_LINE_ATTACK: u32 = 2;
+pub const TETRIS_ATTACK: u32 = 4;
+pub const MAX_GARBAGE_PER_LOCK: u32 = 8;
+
 // Levels //
 
 pub const START_TETRIS_LEVEL: u32 = 1;
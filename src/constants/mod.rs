@@ -1,3 +1,5 @@
 pub mod piece;
 pub mod window;
-pub mod game;
\ No newline at end of file
+pub mod game;
+pub mod controls;
+pub mod ai;
\ No newline at end of file
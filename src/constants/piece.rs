@@ -1,6 +1,9 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 use crate::engine::tile::tile::TileId;
 use crate::engine::utility::random::random;
 use crate::engine::utility::types::Coordinate;
+use crate::piece_set::{piece_set, standard_piece_set};
 
 /**
  * Tetrinomino constants and associated functions
@@ -128,74 +131,135 @@ const Z_PIECE: RawPieceData = &[
 pub type Shape = Vec<Coordinate>;
 pub type ShapeData = Vec<Shape>;
 
+/// Corners of the box around each rotation of the T piece
+pub const T_SPIN_CORNERS: [Coordinate; 4] = [Coordinate::new(0, 0), Coordinate::new(2, 0), Coordinate::new(0, 2), Coordinate::new(2, 2)];
+pub const T_SPIN_MIN_CORNERS: usize = 3; // filled corners needed for a T-spin
+
 const RAW_DATA_TILE: char = '#';
 pub const DEFAULT_ROTATION: usize = 0; // The first index is the default rotation
 
-/// Convert the raw piece data into shape data
-fn get_shape_rotation_coordinates(data: RawPieceData) -> ShapeData {
-  let mut shape_rotations: ShapeData = Vec::new();
-  for rotation in data {
-    let mut shape: Vec<Coordinate> = Vec::new();
-    for (y, row) in rotation.iter().enumerate() {
-      for (x, cell) in row.chars().enumerate() {
-        if cell == RAW_DATA_TILE {
-          let coordinate = Coordinate::new(x as i32, y as i32);
-          shape.push(coordinate);
-        }
-      };
+/// Rotate in place, the kick tried by pieces without a kick table
+const NO_KICKS: [Coordinate; 1] = [Coordinate::new(0, 0)];
+
+/// Convert the rows of one rotation of raw piece data into a shape
+pub fn parse_shape(rows: &[impl AsRef<str>]) -> Shape {
+  let mut shape: Shape = Vec::new();
+  for (y, row) in rows.iter().enumerate() {
+    for (x, cell) in row.as_ref().chars().enumerate() {
+      if cell == RAW_DATA_TILE {
+        shape.push(Coordinate::new(x as i32, y as i32));
+      }
     };
-    shape_rotations.push(shape);
   };
-  shape_rotations
+  shape
+}
+
+/// Convert the raw piece data into shape data
+fn get_shape_rotation_coordinates(data: RawPieceData) -> ShapeData {
+  data.iter().map(|rotation| parse_shape(rotation)).collect()
 }
 
-/// Primitive data for a Tetrimino
+/// Primitive data for a piece
+#[derive(Debug, PartialEq)]
 pub struct PieceData {
+  /// the name the piece is written as
+  pub name: String,
   pub tile_id: TileId,
   pub shape: ShapeData,
   /// align shapes better in the preview
   pub preview_offset: Coordinate,
-  /// The shape should spawn against the roof
-  /// So offset by the empty space above the shape in the first rotation.
-  pub offset_y: u32,
+  /// moves the shape from where it would spawn
+  pub spawn_offset: Coordinate,
+  /// offsets tried in order when rotating out of each rotation, the first that fits is taken
+  ///
+  /// Pieces without a kick table only rotate in place.
+  pub kicks: Vec<Vec<Coordinate>>,
 }
 
 impl PieceData {
-  pub fn new(shape: ShapeData, tile_id: TileId, offset_y: u32, preview_offset: Coordinate) -> Self {
-    Self { shape, tile_id, offset_y, preview_offset }
+  pub fn new(name: &str, shape: ShapeData, tile_id: TileId, preview_offset: Coordinate) -> Self {
+    Self { name: String::from(name), shape, tile_id, preview_offset, spawn_offset: Coordinate::default(), kicks: Vec::new() }
+  }
+
+  /// Get the offsets to try when rotating out of `rotation`
+  pub fn kicks(&self, rotation: usize) -> &[Coordinate] {
+    self.kicks.get(rotation).map_or(&NO_KICKS[..], Vec::as_slice)
   }
 }
 
-/// The different types of Tetriminos
-#[derive(Debug)]
-pub enum ShapeType { I, J, L, O, S, T, Z }
+/// Get the piece data of the standard Tetriminos
+pub fn standard_pieces() -> Vec<PieceData> {
+  vec![
+    PieceData::new("I", get_shape_rotation_coordinates(I_PIECE), 1, Coordinate::new(0, -1)),
+    PieceData::new("J", get_shape_rotation_coordinates(J_PIECE), 2, Coordinate::new(1, 0)),
+    PieceData::new("L", get_shape_rotation_coordinates(L_PIECE), 3, Coordinate::new(1, 0)),
+    PieceData::new("O", get_shape_rotation_coordinates(O_PIECE), 4, Coordinate::new(2, 1)),
+    PieceData::new("S", get_shape_rotation_coordinates(S_PIECE), 5, Coordinate::new(1, 0)),
+    PieceData::new("T", get_shape_rotation_coordinates(T_PIECE), 6, Coordinate::new(1, 0)),
+    PieceData::new("Z", get_shape_rotation_coordinates(Z_PIECE), 7, Coordinate::new(1, 0)),
+  ]
+}
+
+/// A kind of piece, named by the piece set in play
+///
+/// The standard Tetriminos can always be named, and are taken from the standard set when the set in play doesn't
+/// have them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShapeType(&'static str);
 
 impl ShapeType {
+  pub const I: ShapeType = ShapeType("I");
+  pub const J: ShapeType = ShapeType("J");
+  pub const L: ShapeType = ShapeType("L");
+  pub const O: ShapeType = ShapeType("O");
+  pub const S: ShapeType = ShapeType("S");
+  pub const T: ShapeType = ShapeType("T");
+  pub const Z: ShapeType = ShapeType("Z");
+
+  pub const STANDARD: [ShapeType; 7] = [ShapeType::I, ShapeType::J, ShapeType::L, ShapeType::O, ShapeType::S, ShapeType::T, ShapeType::Z];
+
+  /// Get every shape of the piece set in play
+  pub fn all() -> Vec<Self> {
+    piece_set().pieces().iter().map(|data| ShapeType(&data.name)).collect()
+  }
+
   /// Get the piece data for the shape
-  pub fn data(&self) -> PieceData {
-    match self {
-      ShapeType::I => PieceData::new(get_shape_rotation_coordinates(I_PIECE), 1, 2, Coordinate::new(0, -1)),
-      ShapeType::J => PieceData::new(get_shape_rotation_coordinates(J_PIECE), 2, 1, Coordinate::new(1, 0)),
-      ShapeType::L => PieceData::new(get_shape_rotation_coordinates(L_PIECE), 3, 1, Coordinate::new(1, 0)),
-      ShapeType::O => PieceData::new(get_shape_rotation_coordinates(O_PIECE), 4, 0, Coordinate::new(2, 1)),
-      ShapeType::S => PieceData::new(get_shape_rotation_coordinates(S_PIECE), 5, 1, Coordinate::new(1, 0)),
-      ShapeType::T => PieceData::new(get_shape_rotation_coordinates(T_PIECE), 6, 1, Coordinate::new(1, 0)),
-      ShapeType::Z => PieceData::new(get_shape_rotation_coordinates(Z_PIECE), 7, 1, Coordinate::new(1, 0)),
-    }
+  pub fn data(&self) -> &'static PieceData {
+    piece_set().get(self.0)
+      .or_else(|| standard_piece_set().get(self.0))
+      .expect("failed to find piece data")
+  }
+
+  /// Get the name of the shape
+  pub fn name(&self) -> &'static str {
+    self.0
+  }
+
+  /// Get the shape named `name` in the piece set in play, or the standard Tetrimino named `name`
+  pub fn from_name(name: &str) -> Option<Self> {
+    piece_set().get(name)
+      .or_else(|| standard_piece_set().get(name))
+      .map(|data| ShapeType(&data.name))
   }
 
-  /// return a random shape
+  /// return a random shape of the piece set in play
   pub fn random() -> Self {
-    let index = random(0, 7);
-    match index {
-      0 => ShapeType::I,
-      1 => ShapeType::J,
-      2 => ShapeType::L,
-      3 => ShapeType::O,
-      4 => ShapeType::S,
-      5 => ShapeType::T,
-      6 => ShapeType::Z,
-      _ => panic!("random shape index out of bounds"),
-    }
+    let all = Self::all();
+    all[random(0, all.len())]
+  }
+}
+
+impl Serialize for ShapeType {
+  /// Write the shape as its name
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.name())
+  }
+}
+
+impl<'de> Deserialize<'de> for ShapeType {
+  /// Read the shape from its name
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Self::from_name(&name).ok_or_else(|| de::Error::custom(format!("unknown piece {name}")))
   }
 }
@@ -6,6 +6,9 @@ use crate::engine::render::color::{color, RGBA};
  */
 
 pub const TITLE: &str = "Tetris";
+pub const ORGANIZATION: &str = "aardhyn"; // user data is stored under the organization and application
+pub const APPLICATION: &str = "tetris";
 pub const SCREEN_COLOR: RGBA = color::MANTLE;
+pub const LETTERBOX_COLOR: RGBA = color::CRUST; // fills the window around the screen when it doesn't fit exactly
 pub const WINDOW_DIMENSIONS: Vec2<u32> = Vec2::new(950, 900);
 pub const SCREEN_PIXELS: Vec2<u32> = Vec2::new(190, 180);
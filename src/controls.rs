@@ -0,0 +1,31 @@
+use sdl2::keyboard::Keycode;
+
+use crate::board::BoardInput;
+use crate::engine::event::EventStore;
+
+/**
+ * Key bindings for controlling a board
+ */
+
+/// The keys a player uses to control their board
+#[derive(Clone, Copy, Debug)]
+pub struct Controls {
+  pub left: Keycode,
+  pub right: Keycode,
+  pub down: Keycode,
+  pub rotate: Keycode,
+  pub hard_drop: Keycode,
+}
+
+impl Controls {
+  /// Build the input for a board from the keys in `events`
+  pub fn poll(&self, events: &EventStore) -> BoardInput {
+    BoardInput {
+      left: events.is_key_held(self.left),
+      right: events.is_key_held(self.right),
+      down: events.is_key_held(self.down),
+      rotate: events.is_key_pressed(self.rotate),
+      hard_drop: events.is_key_pressed(self.hard_drop),
+    }
+  }
+}
@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use sdl2::mouse::MouseButton;
+
+use crate::board::Board;
+use crate::constants::controls::{EDITOR_BRUSH, EDITOR_CLEAR, EDITOR_DOWN, EDITOR_ERASE, EDITOR_LEFT, EDITOR_PAINT, EDITOR_PLAY, EDITOR_QUEUE_KEYS, EDITOR_RIGHT, EDITOR_SAVE, EDITOR_UNQUEUE, EDITOR_UP};
+use crate::constants::game::{BOARD_DIMENSIONS, BOARD_POSITION, BRUSH_TEXT_POSITION, BRUSH_TILE_POSITION, BUFFER_ROWS, EDITOR_CURSOR_COLOR, EDITOR_HELP, EDITOR_HELP_POSITION, EDITOR_SAVED_TEXT, EDITOR_STATUS_POSITION, FIRST_ROW, GARBAGE_TILE_ID, MATRIX_DIMENSIONS, MENU_SPACING, QUEUE_PIECES_POSITION, QUEUE_SHOWN, QUEUE_TEXT_POSITION};
+use crate::constants::piece::ShapeType;
+use crate::constants::window::{APPLICATION, ORGANIZATION};
+use crate::engine::asset::AssetManager;
+use crate::engine::event::EventStore;
+use crate::engine::geometry::Vec2;
+use crate::engine::render::color::color;
+use crate::engine::render::Renderer;
+use crate::engine::render::text::Text;
+use crate::engine::storage::{read_json, user_path, write_json};
+use crate::engine::tile::tile::TileId;
+use crate::engine::tile::tileset::Tileset;
+use crate::engine::utility::types::Coordinate;
+use crate::setup::Setup;
+
+/**
+ * Paint a board and queue pieces to play from
+ */
+
+/// File in the user data directory the edited setup is saved to
+const SETUP_FILE: &str = "setup.json";
+
+/// Defines the outcome of an editor update
+#[derive(PartialEq, Debug)]
+pub enum EditorEvent {
+  /// Play from the edited board and queue
+  Play,
+  Nothing,
+}
+
+/// A board painted tile by tile and a queue of pieces to play on it
+pub struct Editor {
+  pub board: Board,
+  queue: Vec<ShapeType>,
+  cursor: Coordinate,
+  /// index of the tile painted, from `brushes`
+  brush: usize,
+  mouse_position: Vec2<i32>,
+  path: Option<PathBuf>,
+
+  brush_text: Text,
+  queue_text: Text,
+  queue_pieces_text: Text,
+  help_texts: Vec<Text>,
+  status_text: Text,
+}
+
+impl Editor {
+  /// Instantiate a new editor with the setup saved last, if there is one
+  pub fn new(tileset: Rc<Tileset>) -> Self {
+    let help_texts = EDITOR_HELP.iter()
+      .enumerate()
+      .map(|(index, help)| Text::new(String::from(*help), color::TEXT, EDITOR_HELP_POSITION + Vec2::new(0, index as i32 * MENU_SPACING)))
+      .collect();
+
+    let mut editor = Self {
+      board: Board::new(tileset, BOARD_POSITION, BOARD_DIMENSIONS, BUFFER_ROWS),
+      queue: Vec::new(),
+      cursor: Coordinate::new(0, MATRIX_DIMENSIONS.y as i32 - 1),
+      brush: 0,
+      mouse_position: Vec2::default(),
+      path: user_path(ORGANIZATION, APPLICATION, SETUP_FILE).map_err(|error| eprintln!("{error}")).ok(),
+
+      brush_text: Text::new(String::from("BRUSH"), color::TEXT, BRUSH_TEXT_POSITION),
+      queue_text: Text::new(String::from("QUEUE"), color::TEXT, QUEUE_TEXT_POSITION),
+      queue_pieces_text: Text::new(String::new(), color::TEXT, QUEUE_PIECES_POSITION),
+      help_texts,
+      status_text: Text::new(String::new(), color::TEXT, EDITOR_STATUS_POSITION),
+    };
+    editor.load();
+    editor
+  }
+
+  /// Get the pieces queued to play
+  pub fn queue(&self) -> &[ShapeType] {
+    &self.queue
+  }
+
+  // Rendering //
+
+  /// Render the board, cursor, brush, queue, and key help
+  pub fn render(&mut self, assets: &AssetManager, renderer: &mut Renderer) {
+    self.board.render(renderer, true);
+    self.board.render_outline(renderer, &vec![Coordinate::new(0, 0)], &self.cursor, EDITOR_CURSOR_COLOR);
+
+    let typeface = assets.typefaces
+      .use_store()
+      .get("typeface")
+      .expect("failed to fetch typeface");
+    self.brush_text.render(&typeface, &assets.textures, renderer);
+    self.queue_text.render(&typeface, &assets.textures, renderer);
+    self.queue_pieces_text.render(&typeface, &assets.textures, renderer);
+    self.status_text.render(&typeface, &assets.textures, renderer);
+    for text in &mut self.help_texts {
+      text.render(&typeface, &assets.textures, renderer);
+    }
+
+    // draw a tile of the brush
+    let tileset = self.board.tileset();
+    let tile = tileset.get_tiledata(brushes()[self.brush]).expect("failed to get tile data");
+    renderer.draw_from_texture(&tileset.texture, BRUSH_TILE_POSITION, tile.src);
+  }
+
+  // Update //
+
+  /// Paint and queue with the mouse and keys in `events`
+  pub fn update(&mut self, events: &EventStore) -> EditorEvent {
+    // move the cursor with the mouse or arrows
+    let mouse_position = events.mouse_position();
+    if mouse_position != self.mouse_position {
+      self.mouse_position = mouse_position;
+      if let Some(coordinate) = self.board.coordinate_at(mouse_position) {
+        self.cursor = coordinate;
+      }
+    }
+    for (key, step) in [(EDITOR_UP, Coordinate::new(0, -1)), (EDITOR_DOWN, Coordinate::new(0, 1)), (EDITOR_LEFT, Coordinate::new(-1, 0)), (EDITOR_RIGHT, Coordinate::new(1, 0))] {
+      if events.is_key_pressed(key) {
+        let (width, height) = MATRIX_DIMENSIONS.destructure();
+        let cursor = self.cursor + step;
+        self.cursor = Coordinate::new(cursor.x.clamp(0, width as i32 - 1), cursor.y.clamp(FIRST_ROW, height as i32 - 1));
+      }
+    }
+
+    // paint and erase under the cursor, or the mouse while a button is held
+    let hovered = self.board.coordinate_at(mouse_position);
+    let painted = match hovered.filter(|_| events.is_button_held(MouseButton::Left)) {
+      Some(coordinate) => Some(coordinate),
+      None => events.is_key_pressed(EDITOR_PAINT).then_some(self.cursor),
+    };
+    let erased = match hovered.filter(|_| events.is_button_held(MouseButton::Right)) {
+      Some(coordinate) => Some(coordinate),
+      None => events.is_key_pressed(EDITOR_ERASE).then_some(self.cursor),
+    };
+    if let Some(coordinate) = painted {
+      self.board.fill(&coordinate, brushes()[self.brush]);
+      self.status_text.clear_content();
+    }
+    if let Some(coordinate) = erased {
+      self.board.erase(&coordinate);
+      self.status_text.clear_content();
+    }
+    if events.is_key_pressed(EDITOR_CLEAR) {
+      self.board.clear();
+      self.status_text.clear_content();
+    }
+    if events.is_key_pressed(EDITOR_BRUSH) {
+      self.brush = (self.brush + 1) % brushes().len();
+    }
+
+    // edit the queue
+    for (key, shape_type) in EDITOR_QUEUE_KEYS {
+      if events.is_key_pressed(key) {
+        self.queue.push(shape_type);
+        self.write_queue();
+      }
+    }
+    if events.is_key_pressed(EDITOR_UNQUEUE) {
+      self.queue.pop();
+      self.write_queue();
+    }
+
+    if events.is_key_pressed(EDITOR_SAVE) {
+      self.save();
+    }
+    if events.is_key_pressed(EDITOR_PLAY) {
+      return EditorEvent::Play;
+    }
+    EditorEvent::Nothing
+  }
+
+  /// Update the queue text with the queued pieces
+  fn write_queue(&mut self) {
+    let mut pieces: String = self.queue.iter().take(QUEUE_SHOWN).map(|shape_type| shape_type.name()).collect();
+    if self.queue.len() > QUEUE_SHOWN {
+      pieces.push_str("...");
+    }
+    self.queue_pieces_text.set_content(pieces);
+    self.status_text.clear_content();
+  }
+
+  /// Replace the board and queue with the saved setup, if there is one
+  fn load(&mut self) {
+    let Some(path) = self.path.as_deref().filter(|path| path.exists()) else {
+      return;
+    };
+
+    let setup: Result<Setup, String> = read_json(path);
+    let loaded = setup.and_then(|setup| Ok((setup.cells()?, setup.shape_types()?)));
+    match loaded {
+      Ok((cells, queue)) => {
+        for (coordinate, tile_id) in cells {
+          self.board.fill(&coordinate, tile_id);
+        }
+        self.queue = queue;
+        self.write_queue();
+      }
+      Err(error) => eprintln!("{error}"),
+    }
+  }
+
+  /// Save the board and queue
+  fn save(&mut self) {
+    let Some(path) = &self.path else {
+      return;
+    };
+
+    let setup = Setup::new(&self.board.cells(), &self.queue);
+    match write_json(path, &setup) {
+      Ok(()) => self.status_text.set_content(String::from(EDITOR_SAVED_TEXT)),
+      Err(error) => eprintln!("{error}"),
+    }
+  }
+}
+
+/// Get the tiles that can be painted, each standard piece then garbage
+fn brushes() -> Vec<TileId> {
+  ShapeType::STANDARD.iter()
+    .map(|shape_type| shape_type.data().tile_id)
+    .chain([GARBAGE_TILE_ID])
+    .collect()
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::engine::asset::AssetManager;
 use crate::engine::event::EventStore;
 use crate::engine::render::{Properties, Renderer};
@@ -17,6 +19,8 @@ pub struct Actions<TState> {
   pub update: fn(&EventStore, &AssetManager, &mut TState, &mut Renderer),
   /// Set up the state
   pub setup: fn(&AssetManager) -> TState,
+  /// Act on the state before the application closes
+  pub quit: fn(&mut TState),
 }
 
 /// Bundles a subsystem with actions
@@ -45,15 +49,30 @@ impl<'a, TState> Application<'a, TState> {
     loop {
       self.subsystem.events.update(&mut self.event_store);
       if self.subsystem.events.is_quit {
+        (self.actions.quit)(&mut state);
         break;
       }
 
       (self.actions.update)(&self.event_store, assets, &mut state, &mut self.subsystem.renderer);
       (self.actions.render)(&mut state, assets, &mut self.subsystem.renderer);
+      if let Some(path) = self.subsystem.renderer.take_screenshot_request() {
+        self.screenshot(&mut state, assets, &path).map_err(|error| eprintln!("{error}")).ok();
+      }
 
       self.subsystem.renderer.present();
     }
   }
+
+  /// Render the frame again into a texture at the logical resolution and save it to `path` as a PNG
+  fn screenshot(&mut self, state: &mut TState, assets: &AssetManager, path: &Path) -> Result<(), String> {
+    let renderer = &mut self.subsystem.renderer;
+    let frame = renderer.create_target(renderer.logical_size())?;
+    renderer.with_target(&frame, |renderer| {
+      renderer.clear();
+      (self.actions.render)(state, assets, renderer);
+    });
+    renderer.save_target(&frame, path)
+  }
 }
 
 /// Build subsystems and build application of `Properties` `TState` with `Actions`
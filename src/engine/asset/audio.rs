@@ -35,18 +35,33 @@ type AudioStore = Store<Audio>;
 /// load and play music and sfx
 pub struct AudioPlayer {
   store: AudioStore,
+  /// play sounds, false when there is no audio device
+  enabled: bool,
 }
 
 impl AudioPlayer {
-  /// Instantiate a new audio player
+  /// Instantiate a new audio player, staying silent if the audio device can't be opened
   pub fn new() -> Self {
-    initialize_audio_subsystem()
-      .expect("Failed to initialize audio subsystem");
-    Self { store: AudioStore::new() }
+    match initialize_audio_subsystem() {
+      Ok(()) => Self { store: AudioStore::new(), enabled: true },
+      Err(error) => {
+        eprintln!("failed to initialize audio subsystem, playing without sound: {error}");
+        Self::silent()
+      }
+    }
+  }
+
+  /// Instantiate an audio player that loads and plays nothing, without opening the audio device
+  pub fn silent() -> Self {
+    Self { store: AudioStore::new(), enabled: false }
   }
 
   /// Load a sfx or music file
   pub fn load(&mut self, sound_type: SoundType, filepath: String) -> Result<(), &str> {
+    if !self.enabled {
+      return Ok(());
+    }
+
     let path = filepath.clone();
     let filename = path.split("/").last().ok_or("Failed to get filename")?;
     let basename = filename.split(".").next().ok_or("Failed to get basename")?;
@@ -69,6 +84,10 @@ impl AudioPlayer {
 
   /// Play a sfx or music
   pub fn play(&self, name: &str, volume: i32, looping: Loop) -> Result<(), String> {
+    if !self.enabled {
+      return Ok(());
+    }
+
     let audio = self.store.get(name)?;
     let loops = match looping {
       Loop::Forever => -1,
@@ -91,6 +110,10 @@ impl AudioPlayer {
 
   /// Stop a playing sfx or music
   pub fn stop(&self, name: &str) -> Result<(), String> {
+    if !self.enabled {
+      return Ok(());
+    }
+
     let audio = self.store.get(name)?;
     match &audio.sound {
       Sound::Music { data: _ } => sdl2::mixer::Music::halt(),
@@ -30,11 +30,11 @@ pub struct AssetManager<'ttf> {
 }
 
 impl<'ttf> AssetManager<'ttf> {
-  /// Instantiate a new asset manager
+  /// Instantiate a new asset manager, without sound for a headless `renderer`
   pub fn new(renderer: &Renderer, ttf_context: &'ttf Sdl2TtfContext) -> Self {
     Self {
       textures: TextureLoader::new(renderer.new_texture_creator()),
-      audio: AudioPlayer::new(),
+      audio: if renderer.is_headless() { AudioPlayer::silent() } else { AudioPlayer::new() },
       tilesets: TilesetStore::new(),
       typefaces: TypefaceLoader::new(&ttf_context),
     }
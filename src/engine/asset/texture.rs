@@ -1,8 +1,10 @@
+use std::cell::RefCell;
 use std::rc::Rc;
 
 use sdl2::image::LoadTexture;
+use sdl2::pixels::PixelFormatEnum;
 use sdl2::render::{TextureCreator, TextureQuery};
-use sdl2::surface::Surface;
+use sdl2::surface::{Surface, SurfaceContext};
 use sdl2::video::WindowContext;
 
 use crate::engine::geometry::{Rec2, Vec2};
@@ -15,15 +17,50 @@ use crate::engine::store::HeapStore;
 /// store textures
 pub type TextureStore = HeapStore<Texture>;
 
+/// Creates textures for the window, or for the surface of a headless renderer
+pub enum Creator {
+  Window(TextureCreator<WindowContext>),
+  Surface(TextureCreator<SurfaceContext<'static>>),
+}
+
+impl Creator {
+  /// Load a texture from an image file
+  pub fn load_texture(&self, filepath: &str) -> Result<sdl2::render::Texture, String> {
+    match self {
+      Creator::Window(creator) => creator.load_texture(filepath),
+      Creator::Surface(creator) => creator.load_texture(filepath),
+    }
+  }
+
+  /// Build a texture from the pixels of `surface`
+  pub fn build_from_surface(&self, surface: Surface) -> Result<sdl2::render::Texture, String> {
+    match self {
+      Creator::Window(creator) => creator.create_texture_from_surface(surface),
+      Creator::Surface(creator) => creator.create_texture_from_surface(surface),
+    }
+    .map_err(|error| error.to_string())
+  }
+
+  /// Build a texture of `size` that can be rendered to
+  pub fn build_target(&self, size: Vec2<u32>) -> Result<sdl2::render::Texture, String> {
+    let format = PixelFormatEnum::RGBA8888;
+    match self {
+      Creator::Window(creator) => creator.create_texture_target(format, size.x, size.y),
+      Creator::Surface(creator) => creator.create_texture_target(format, size.x, size.y),
+    }
+    .map_err(|error| error.to_string())
+  }
+}
+
 /// Load and store textures
 pub struct TextureLoader {
   store: TextureStore,
-  subsystem: TextureCreator<WindowContext>,
+  subsystem: Creator,
 }
 
 impl TextureLoader {
   /// Instantiate a new texture loader
-  pub fn new(creator: TextureCreator<WindowContext>) -> Self {
+  pub fn new(creator: Creator) -> Self {
     let store = HeapStore::new();
     Self { subsystem: creator, store }
   }
@@ -43,7 +80,7 @@ impl TextureLoader {
   /// Builds a texture from a surface
   pub fn build_from_surface(&self, surface: Surface) -> Result<Texture, &str> {
     let internal_texture = self.subsystem
-      .create_texture_from_surface(surface)
+      .build_from_surface(surface)
       .map_err(|_| "Failed to load texture")?;
     let texture = Texture::new(internal_texture);
     Ok(texture)
@@ -60,7 +97,8 @@ pub type SrcRect = Rec2<u32, u32>;
 
 /// A wrapper for a SDL2 texture
 pub struct Texture {
-  pub internal: sdl2::render::Texture,
+  /// mutable so draws can tint the shared texture
+  pub internal: RefCell<sdl2::render::Texture>,
   pub dimensions: Vec2<u32>,
 }
 
@@ -69,6 +107,6 @@ impl Texture {
   pub fn new(texture: sdl2::render::Texture) -> Self {
     let TextureQuery { width, height, .. } = texture.query();
     let dimensions = Vec2::new(width, height);
-    Self { internal: texture, dimensions }
+    Self { internal: RefCell::new(texture), dimensions }
   }
 }
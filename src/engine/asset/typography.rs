@@ -28,13 +28,15 @@ impl<'ttf, 'l> TypefaceLoader<'ttf, 'l> {
 
   /// Loads a typeface from a file and adds it to the store
   pub fn load(&mut self, filepath: String, size: u16) -> Result<(), String> {
-    let font = self.subsystem.load_font(filepath.as_str(), size)?;
-
     let filename = filepath.split("/").last().ok_or("Failed to get filename")?;
     let basename = filename.split(".").next().ok_or("Failed to get basename")?;
+    self.load_as(String::from(basename), &filepath, size)
+  }
 
-    self.store.add(String::from(basename), Rc::new(font));
-
+  /// Loads a typeface from a file and adds it to the store as `name`, so one file can be loaded in several sizes
+  pub fn load_as(&mut self, name: String, filepath: &str, size: u16) -> Result<(), String> {
+    let font = self.subsystem.load_font(filepath, size)?;
+    self.store.add(name, Rc::new(font));
     Ok(())
   }
 
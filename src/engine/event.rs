@@ -1,7 +1,8 @@
 use std::collections::hash_set::HashSet;
 
-use sdl2::event::Event;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
 
 use crate::engine::geometry::Vec2;
 
@@ -11,12 +12,20 @@ use crate::engine::geometry::Vec2;
 
 /// A set of keycodes
 type KeyStore = HashSet<Keycode>;
+/// A set of mouse buttons
+type ButtonStore = HashSet<MouseButton>;
 
 /// A store of events
 pub struct EventStore {
   held_keys: KeyStore,
   pressed_keys: KeyStore,
+  held_buttons: ButtonStore,
+  pressed_buttons: ButtonStore,
   mouse_position: Vec2<i32>,
+  /// size the window was resized to this frame
+  window_size: Option<Vec2<u32>>,
+  /// position the window was moved to this frame
+  window_position: Option<Vec2<i32>>,
 }
 
 impl EventStore {
@@ -25,7 +34,11 @@ impl EventStore {
     Self {
       pressed_keys: HashSet::new(),
       held_keys: HashSet::new(),
+      pressed_buttons: HashSet::new(),
+      held_buttons: HashSet::new(),
       mouse_position: Vec2::default(),
+      window_size: None,
+      window_position: None,
     }
   }
 
@@ -43,10 +56,36 @@ impl EventStore {
     // no need to remove from `pressed_keys` as it will be cleared at the start of the next frame
     self.held_keys.remove(&keycode);
   }
+  /// Clear the pressed mouse buttons from the store
+  pub fn clear_pressed_buttons(&mut self) {
+    self.pressed_buttons.clear();
+  }
+  /// Mark a mouse button as pressed
+  pub fn press_button(&mut self, button: MouseButton) {
+    self.pressed_buttons.insert(button);
+    self.held_buttons.insert(button);
+  }
+  /// Mark a mouse button as released
+  pub fn raise_button(&mut self, button: MouseButton) {
+    self.held_buttons.remove(&button);
+  }
   /// Mark the location of the mouse
   pub fn set_mose_position(&mut self, position: Vec2<i32>) {
     self.mouse_position = position;
   }
+  /// Clear the window changes from the store
+  pub fn clear_window_changes(&mut self) {
+    self.window_size = None;
+    self.window_position = None;
+  }
+  /// Mark the size the window was resized to
+  pub fn resize_window(&mut self, size: Vec2<u32>) {
+    self.window_size = Some(size);
+  }
+  /// Mark the position the window was moved to
+  pub fn move_window(&mut self, position: Vec2<i32>) {
+    self.window_position = Some(position);
+  }
 
   /// Query if the key was pressed this frame.
   pub fn is_key_pressed(&self, keycode: Keycode) -> bool {
@@ -56,6 +95,26 @@ impl EventStore {
   pub fn is_key_held(&self, keycode: Keycode) -> bool {
     self.held_keys.contains(&keycode)
   }
+  /// Query if the mouse button was pressed this frame.
+  pub fn is_button_pressed(&self, button: MouseButton) -> bool {
+    self.pressed_buttons.contains(&button)
+  }
+  /// Query if the mouse button is currently held down.
+  pub fn is_button_held(&self, button: MouseButton) -> bool {
+    self.held_buttons.contains(&button)
+  }
+  /// Get the location of the mouse
+  pub fn mouse_position(&self) -> Vec2<i32> {
+    self.mouse_position
+  }
+  /// Get the size the window was resized to this frame, if it was
+  pub fn window_resized(&self) -> Option<Vec2<u32>> {
+    self.window_size
+  }
+  /// Get the position the window was moved to this frame, if it was
+  pub fn window_moved(&self) -> Option<Vec2<i32>> {
+    self.window_position
+  }
 }
 
 /// Manage events polled by SDL2
@@ -77,6 +136,8 @@ impl Events {
   /// Poll for events and update `event_store`
   pub fn update(&mut self, event_store: &mut EventStore) {
     event_store.clear_pressed_keys();
+    event_store.clear_pressed_buttons();
+    event_store.clear_window_changes();
     
     let events = self.event_pump.poll_iter();
     for event in events {
@@ -97,6 +158,19 @@ impl Events {
         Event::MouseMotion { x, y, .. } => {
           event_store.set_mose_position(Vec2 { x, y });
         }
+        Event::MouseButtonDown { mouse_btn, x, y, .. } => {
+          event_store.set_mose_position(Vec2 { x, y });
+          event_store.press_button(mouse_btn);
+        }
+        Event::MouseButtonUp { mouse_btn, .. } => {
+          event_store.raise_button(mouse_btn);
+        }
+        Event::Window { win_event: WindowEvent::Resized(w, h), .. } => {
+          event_store.resize_window(Vec2::new(w.max(0) as u32, h.max(0) as u32));
+        }
+        Event::Window { win_event: WindowEvent::Moved(x, y), .. } => {
+          event_store.move_window(Vec2::new(x, y));
+        }
         _ => {}
       }
     }
@@ -1,7 +1,9 @@
 use std::marker::Copy;
+use std::ops::Add;
 
 use num::{Num, Unsigned};
 use sdl2::rect::{Point, Rect};
+use serde::{Deserialize, Serialize};
 
 /**
  * Geometric primitives
@@ -29,7 +31,7 @@ impl<T: UnitPrimitive + Into<i32>> IntConvertable for T {}
 // Vector 2D //
 
 /// A vector representation in 2D space of some numeric type `T`
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct Vec2<T>
   where
     T: UnitPrimitive,
@@ -49,6 +51,15 @@ impl<T: UnitPrimitive> Vec2<T> {
   }
 }
 
+impl<T: UnitPrimitive> Add for Vec2<T> {
+  type Output = Self;
+
+  /// Add the components of two vectors
+  fn add(self, other: Self) -> Self::Output {
+    Self::new(self.x + other.x, self.y + other.y)
+  }
+}
+
 impl<T: IntConvertable> From<Vec2<T>> for Point {
   /// Convert a Vec2 of T to a Point of i32
   fn from(value: Vec2<T>) -> Self {
@@ -82,6 +93,10 @@ impl<T: UnitPrimitive, U: SizePrimitive> Rec2<T, U> {
   pub fn destructure(&self) -> ((T, T), (U, U)) {
     (self.origin.destructure(), self.size.destructure())
   }
+  /// Create a copy of the rectangle with its origin moved by `offset`
+  pub fn translate(&self, offset: Vec2<T>) -> Self {
+    Self::new(self.origin + offset, self.size)
+  }
 }
 
 impl<T: IntConvertable, U: SizePrimitive> From<Rec2<T, U>> for Rect {
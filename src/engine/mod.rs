@@ -5,6 +5,8 @@ pub mod render;
 pub mod tile;
 pub mod utility;
 pub mod time;
+pub mod tween;
 pub mod subsystem;
 pub mod application;
-pub mod store;
\ No newline at end of file
+pub mod store;
+pub mod storage;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use sdl2::pixels::Color;
+use serde::{Deserialize, Serialize};
 
 /**
  * Color representation and constants
@@ -6,8 +7,9 @@ use sdl2::pixels::Color;
 
 // Color //
 
-/// RGBA color
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
+/// RGBA color, written as `[red, green, blue, alpha]`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[u8; 4]", into = "[u8; 4]")]
 pub struct RGBA {
   pub red: u8,
   pub green: u8,
@@ -26,6 +28,20 @@ impl RGBA {
   }
 }
 
+impl From<[u8; 4]> for RGBA {
+  /// Convert `[red, green, blue, alpha]` to an `RGBA` color
+  fn from([red, green, blue, alpha]: [u8; 4]) -> Self {
+    Self::new(red, green, blue, alpha)
+  }
+}
+
+impl From<RGBA> for [u8; 4] {
+  /// Convert an `RGBA` color to `[red, green, blue, alpha]`
+  fn from(value: RGBA) -> Self {
+    [value.red, value.green, value.blue, value.alpha]
+  }
+}
+
 impl From<&RGBA> for Color {
   /// Convert reference to `RGBA` color to an `sdl2::pixels::Color`
   fn from(value: &RGBA) -> Self {
@@ -44,6 +60,7 @@ impl From<RGBA> for Color {
 
 pub const U8MAX: u8 = 255;
 pub const OPAQUE: u8 = U8MAX;
+pub const UNTINTED: RGBA = RGBA::new(U8MAX, U8MAX, U8MAX, OPAQUE); // leaves textures as they are when tinting
 
 // common //
 
@@ -52,6 +69,9 @@ pub mod color {
 
   // From the Catppuccin theme:
   pub const TEXT: RGBA = RGBA::new(205, 214, 244, OPAQUE);
+  pub const RED: RGBA = RGBA::new(243, 139, 168, OPAQUE);
+  pub const OVERLAY_0: RGBA = RGBA::new(108, 112, 134, OPAQUE);
   pub const SURFACE_0: RGBA = RGBA::new(49, 50, 68, OPAQUE);
   pub const MANTLE: RGBA = RGBA::new(24, 24, 37, OPAQUE);
+  pub const CRUST: RGBA = RGBA::new(17, 17, 27, OPAQUE);
 }
\ No newline at end of file
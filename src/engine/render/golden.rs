@@ -0,0 +1,79 @@
+use std::{env, fs};
+use std::path::{Path, PathBuf};
+
+use sdl2::image::LoadSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+
+use crate::engine::geometry::Vec2;
+use crate::engine::render::Renderer;
+
+/**
+ * Compare frames drawn by a headless renderer against stored golden images
+ */
+
+/// Environment variable that saves frames as the golden images instead of comparing against them when set
+pub const UPDATE_GOLDEN_VAR: &str = "UPDATE_GOLDEN";
+
+/// Compare the frame drawn by a headless `renderer` against the golden PNG at `path`
+///
+/// pixels match when no channel differs by more than `tolerance`. When the frame doesn't match, or there is no golden
+/// image at `path`, it's saved beside the golden image with an `.actual.png` extension to inspect. With
+/// `UPDATE_GOLDEN_VAR` set, the frame is saved to `path` as the golden image instead.
+pub fn compare_golden(renderer: &Renderer, path: &Path, tolerance: u8) -> Result<(), String> {
+  if env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+    if let Some(directory) = path.parent() {
+      fs::create_dir_all(directory).map_err(|error| format!("failed to create {}: {error}", directory.display()))?;
+    }
+    return renderer.save_frame(path);
+  }
+  if !path.exists() {
+    renderer.save_frame(&actual_path(path))?;
+    return Err(format!("{} is missing, set {UPDATE_GOLDEN_VAR} to save the frame as the golden image", path.display()));
+  }
+
+  let (size, pixels) = renderer.frame()?;
+  let (golden_size, golden) = read_golden(path)?;
+  if size != golden_size {
+    renderer.save_frame(&actual_path(path))?;
+    return Err(format!(
+      "frame is {}x{} but {} is {}x{}",
+      size.x, size.y, path.display(), golden_size.x, golden_size.y
+    ));
+  }
+
+  let mismatched = pixels
+    .chunks(4)
+    .zip(golden.chunks(4))
+    .filter(|(pixel, expected)| pixel.iter().zip(expected.iter()).any(|(a, b)| a.abs_diff(*b) > tolerance))
+    .count();
+  if mismatched > 0 {
+    renderer.save_frame(&actual_path(path))?;
+    return Err(format!("{mismatched} pixels differ from {}", path.display()));
+  }
+
+  Ok(())
+}
+
+/// Read the size and pixels of the golden PNG at `path` as red, green, blue, then alpha bytes
+fn read_golden(path: &Path) -> Result<(Vec2<u32>, Vec<u8>), String> {
+  let surface = Surface::from_file(path)
+    .map_err(|error| format!("failed to load {}: {error}", path.display()))?
+    .convert_format(PixelFormatEnum::ABGR8888)?;
+
+  let (w, h) = (surface.width(), surface.height());
+  let pitch = surface.pitch() as usize;
+  let row = w as usize * 4;
+  let pixels = surface.without_lock()
+    .ok_or(format!("failed to read {}", path.display()))?
+    .chunks(pitch)
+    .flat_map(|line| &line[..row])
+    .copied()
+    .collect();
+  Ok((Vec2::new(w, h), pixels))
+}
+
+/// Get the path the frame is saved to when it doesn't match the golden image at `path`
+fn actual_path(path: &Path) -> PathBuf {
+  path.with_extension("actual.png")
+}
@@ -1,18 +1,27 @@
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use sdl2::rect::Rect;
-use sdl2::render::TextureCreator;
-use sdl2::video::{FullscreenType, WindowContext};
+use serde::{Deserialize, Serialize};
+use sdl2::gfx::primitives::DrawRenderer;
+use sdl2::image::SaveSurface;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, SurfaceCanvas, WindowCanvas};
+use sdl2::surface::Surface;
+use sdl2::video::FullscreenType;
 
-use crate::engine::asset::texture::{SrcRect, Texture};
+use crate::engine::asset::texture::{Creator, SrcRect, Texture};
 use crate::engine::geometry::{IntConvertable, Rec2, SizePrimitive, Vec2};
-use crate::engine::render::color::RGBA;
+use crate::engine::render::color::{RGBA, UNTINTED};
 
 /**
  * Rendering subsystem
  */
 
 pub mod color;
+pub mod golden;
+pub mod particle;
+pub mod shake;
 pub mod text;
 
 /// Properties required to create a new `Renderer`
@@ -20,20 +29,88 @@ pub mod text;
 pub struct Properties {
   pub title: String,
   pub dimensions: Vec2<u32>,
+  /// where the window is placed on the desktop, left to the system if `None`
+  pub position: Option<Vec2<i32>>,
   pub logical: Option<Vec2<u32>>,
+  pub scaling: Scaling,
   pub fullscreen: bool,
+  pub resizable: bool,
   pub show_cursor: bool,
   pub vsync: bool,
   pub opengl: bool,
   pub hardware_acceleration: bool,
   pub software_acceleration: bool,
   pub screen_color: RGBA,
+  /// color of the bars around the logical resolution when it doesn't fill the window
+  pub letterbox_color: RGBA,
 }
 
-/// Wrapper around `sdl2::render::WindowCanvas`
+/// How the logical resolution is scaled to fill the window
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scaling {
+  /// scale by whole numbers only, so every logical pixel is the same size
+  #[default]
+  Integer,
+  /// scale as large as fits the window, keeping the aspect ratio
+  Fit,
+}
+
+/// Format of frames read back from a renderer, red, green, blue, then alpha bytes
+const FRAME_FORMAT: PixelFormatEnum = PixelFormatEnum::ABGR8888;
+
+/// How drawn pixels are combined with the pixels already on screen
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Blend {
+  /// replace the pixels beneath
+  None,
+  /// mix with the pixels beneath by alpha
+  #[default]
+  Alpha,
+  /// brighten the pixels beneath
+  Add,
+  /// darken the pixels beneath by multiplying their colors
+  Modulate,
+}
+
+impl From<Blend> for BlendMode {
+  /// Convert a `Blend` to an `sdl2::render::BlendMode`
+  fn from(value: Blend) -> Self {
+    match value {
+      Blend::None => BlendMode::None,
+      Blend::Alpha => BlendMode::Blend,
+      Blend::Add => BlendMode::Add,
+      Blend::Modulate => BlendMode::Mod,
+    }
+  }
+}
+
+/// What a renderer draws onto
+enum Canvas {
+  /// a window on screen
+  Window(WindowCanvas),
+  /// pixels in memory, drawn in software without a window
+  Surface(SurfaceCanvas<'static>),
+}
+
+/// Evaluate `$body` with `$canvas` bound to the canvas `$subsystem` holds, whichever kind it is
+macro_rules! with_canvas {
+  ($subsystem:expr, $canvas:ident => $body:expr) => {
+    match $subsystem {
+      Canvas::Window($canvas) => $body,
+      Canvas::Surface($canvas) => $body,
+    }
+  };
+}
+
+/// Wrapper around an `sdl2::render::Canvas` of a window, or of a surface when headless
 pub struct Renderer {
-  subsystem: sdl2::render::WindowCanvas,
+  subsystem: Canvas,
   properties: Properties,
+  /// how draws are blended with the screen
+  blend: Blend,
+  /// where to save the next frame presented
+  screenshot: Option<PathBuf>,
 }
 
 impl Renderer {
@@ -57,76 +134,383 @@ impl Renderer {
     if let Some(size) = properties.logical {
       subsystem.set_logical_size(size.x, size.y).map_err(|e| e.to_string())?;
     }
+    subsystem.set_integer_scale(properties.scaling == Scaling::Integer).map_err(|e| e.to_string())?;
 
     subsystem.set_draw_color(properties.screen_color);
 
     Ok(Self {
-      subsystem,
+      subsystem: Canvas::Window(subsystem),
       properties,
+      blend: Blend::default(),
+      screenshot: None,
     })
   }
 
-  /// Instantiate a new `TextureCreator` from the `Renderer`
-  pub fn new_texture_creator(&self) -> TextureCreator<WindowContext> { self.subsystem.texture_creator() }
+  /// Instantiate a new `Renderer` that draws into pixels in memory of `properties.dimensions`, without a window or GPU
+  ///
+  /// window properties are ignored, and the pixels drawn can be read with `frame` and `save_frame`
+  pub fn headless(properties: Properties) -> Result<Self, String> {
+    let (w, h) = properties.dimensions.destructure();
+    let surface = Surface::new(w, h, FRAME_FORMAT)?;
+    let mut subsystem = surface.into_canvas()?;
+
+    if let Some(size) = properties.logical {
+      subsystem.set_logical_size(size.x, size.y).map_err(|e| e.to_string())?;
+    }
+
+    subsystem.set_draw_color(properties.screen_color);
+
+    Ok(Self {
+      subsystem: Canvas::Surface(subsystem),
+      properties,
+      blend: Blend::default(),
+      screenshot: None,
+    })
+  }
+
+  /// Instantiate a new `Creator` of textures that can be drawn by the `Renderer`
+  pub fn new_texture_creator(&self) -> Creator {
+    match &self.subsystem {
+      Canvas::Window(canvas) => Creator::Window(canvas.texture_creator()),
+      Canvas::Surface(canvas) => Creator::Surface(canvas.texture_creator()),
+    }
+  }
 
   /// Set the windows fullscreen mode
   pub fn set_fullscreen(&mut self, fullscreen: bool) {
+    let Canvas::Window(canvas) = &mut self.subsystem else {
+      return; // nothing to show fullscreen without a window
+    };
+
     // note: we skip over the `sdl2::video::FullscreenType::True`
     //       I've found this mode to have visual artifacts
     if fullscreen {
-      self.subsystem.window_mut().set_fullscreen(FullscreenType::Desktop).expect("Failed to set dekstop fullscreen")
+      canvas.window_mut().set_fullscreen(FullscreenType::Desktop).expect("Failed to set dekstop fullscreen")
     } else {
-      self.subsystem.window_mut().set_fullscreen(FullscreenType::Off).expect("Failed to set windowed")
+      canvas.window_mut().set_fullscreen(FullscreenType::Off).expect("Failed to set windowed")
     }
   }
   /// Check if the window is in fullscreen mode
   pub fn is_fullscreen(&self) -> bool {
-    self.subsystem.window().fullscreen_state() == FullscreenType::Desktop
+    match &self.subsystem {
+      Canvas::Window(canvas) => canvas.window().fullscreen_state() == FullscreenType::Desktop,
+      Canvas::Surface(_) => false,
+    }
+  }
+
+  /// Set the logical resolution the window is scaled to
+  pub fn set_logical_size(&mut self, size: Vec2<u32>) {
+    with_canvas!(&mut self.subsystem, canvas => canvas.set_logical_size(size.x, size.y))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+
+  /// Get the logical resolution the window is scaled to
+  pub fn logical_size(&self) -> Vec2<u32> {
+    let (w, h) = with_canvas!(&self.subsystem, canvas => canvas.logical_size());
+    Vec2::new(w, h)
+  }
+
+  /// Set how the logical resolution is scaled to fill the window
+  pub fn set_scaling(&mut self, scaling: Scaling) {
+    with_canvas!(&mut self.subsystem, canvas => canvas.set_integer_scale(scaling == Scaling::Integer))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+    self.properties.scaling = scaling;
+  }
+  /// Get how the logical resolution is scaled to fill the window
+  pub fn scaling(&self) -> Scaling {
+    self.properties.scaling
+  }
+
+  /// Show or hide the mouse cursor over the window
+  pub fn set_cursor_visible(&self, visible: bool) {
+    if let Canvas::Window(canvas) = &self.subsystem {
+      canvas.window().subsystem().sdl().mouse().show_cursor(visible);
+    }
   }
 
-  /// Set the drawing color of the internal `sdl2::render::WindowCanvas`
+  /// Get the text on the system clipboard
+  pub fn clipboard_text(&self) -> Result<String, String> {
+    self.window_canvas()?.window().subsystem().clipboard().clipboard_text()
+  }
+  /// Put `text` on the system clipboard
+  pub fn set_clipboard_text(&self, text: &str) -> Result<(), String> {
+    self.window_canvas()?.window().subsystem().clipboard().set_clipboard_text(text)
+  }
+  /// Check if the renderer draws into memory rather than a window
+  pub fn is_headless(&self) -> bool {
+    matches!(self.subsystem, Canvas::Surface(_))
+  }
+
+  /// Get the canvas of the window, failing if headless
+  fn window_canvas(&self) -> Result<&WindowCanvas, String> {
+    match &self.subsystem {
+      Canvas::Window(canvas) => Ok(canvas),
+      Canvas::Surface(_) => Err(String::from("there is no window when headless")),
+    }
+  }
+
+  /// Set the drawing color of the internal `sdl2::render::Canvas`
   fn set_color(&mut self, color: RGBA) {
-    self.subsystem.set_draw_color(color);
+    let blend = self.blend.into();
+    with_canvas!(&mut self.subsystem, canvas => {
+      canvas.set_draw_color(color);
+      canvas.set_blend_mode(blend);
+    });
   }
-  /// Clear the screen
+  /// Draw with `blend` for the draws made in `draw`, then return to the blend before
+  pub fn with_blend<F: FnOnce(&mut Self)>(&mut self, blend: Blend, draw: F) {
+    let previous = self.blend;
+    self.blend = blend;
+    draw(self);
+    self.blend = previous;
+  }
+  /// Clear the screen, letterboxing around the logical resolution
   pub fn clear(&mut self) {
-    self.set_color(self.properties.screen_color);
-    self.subsystem.clear();
+    self.fill(self.properties.letterbox_color);
+
+    // clearing ignores the logical resolution, so fill it separately
+    let (w, h) = self.logical_size().destructure();
+    if w > 0 && h > 0 {
+      let screen_color = self.properties.screen_color;
+      self.with_blend(Blend::None, |renderer| renderer.fill_rect(Rec2::new(Vec2::new(0, 0), Vec2::new(w, h)), screen_color));
+    }
+  }
+  /// Set every pixel of the screen or target to `color`
+  fn fill(&mut self, color: RGBA) {
+    with_canvas!(&mut self.subsystem, canvas => {
+      canvas.set_draw_color(color);
+      canvas.set_blend_mode(BlendMode::None);
+      canvas.clear();
+    });
   }
-  /// Present what has been rendered to the screen
+  /// Create a texture of `size` that can be drawn into with `with_target`
+  pub fn create_target(&self, size: Vec2<u32>) -> Result<Texture, String> {
+    let mut internal = self.new_texture_creator().build_target(size)?;
+    internal.set_blend_mode(BlendMode::Blend);
+    Ok(Texture::new(internal))
+  }
+  /// Draw into `target` instead of the screen for the draws made in `draw`, clearing it to transparent first
+  ///
+  /// draws into the target are not scaled to the logical resolution
+  pub fn with_target<F: FnOnce(&mut Self)>(&mut self, target: &Texture, draw: F) {
+    let previous = self.target();
+    let raw = target.internal.borrow().raw();
+    if let Err(error) = self.set_target(raw) {
+      eprintln!("{error}");
+      return;
+    }
+
+    self.fill(RGBA::new(0, 0, 0, 0));
+    draw(self);
+
+    self.set_target(previous).map_err(|error| eprintln!("{error}")).ok();
+  }
+  /// Get the texture being drawn into, or null if drawing to the screen
+  fn target(&self) -> *mut sdl2::sys::SDL_Texture {
+    // SAFETY: the renderer is alive for the call
+    unsafe { sdl2::sys::SDL_GetRenderTarget(self.raw()) }
+  }
+  /// Draw into the texture `raw`, or the screen if null
+  fn set_target(&mut self, raw: *mut sdl2::sys::SDL_Texture) -> Result<(), String> {
+    // SAFETY: the renderer and texture outlive the call, and the texture was created as a target by this renderer
+    let result = unsafe { sdl2::sys::SDL_SetRenderTarget(self.raw(), raw) };
+    if result == 0 { Ok(()) } else { Err(sdl2::get_error()) }
+  }
+  /// Get the internal SDL renderer
+  fn raw(&self) -> *mut sdl2::sys::SDL_Renderer {
+    with_canvas!(&self.subsystem, canvas => canvas.raw())
+  }
+  /// Save the pixels of `target` to `path` as a PNG
+  pub fn save_target(&mut self, target: &Texture, path: &Path) -> Result<(), String> {
+    let (w, h) = target.dimensions.destructure();
+    let previous = self.target();
+    self.set_target(target.internal.borrow().raw())?;
+    let pixels = with_canvas!(&self.subsystem, canvas => canvas.read_pixels(Rect::new(0, 0, w, h), FRAME_FORMAT));
+    self.set_target(previous)?;
+    save_png(&mut pixels?, Vec2::new(w, h), path)
+  }
+
+  /// Get the size of the frame drawn by a headless renderer, and its pixels as red, green, blue, then alpha bytes
+  pub fn frame(&self) -> Result<(Vec2<u32>, Vec<u8>), String> {
+    let Canvas::Surface(canvas) = &self.subsystem else {
+      return Err(String::from("only a headless renderer can read back its frame"));
+    };
+
+    let surface = canvas.surface();
+    let (w, h) = (surface.width(), surface.height());
+    let pitch = surface.pitch() as usize;
+    let row = w as usize * FRAME_FORMAT.byte_size_per_pixel();
+    let pixels = surface.without_lock()
+      .ok_or(String::from("failed to read the frame"))?
+      .chunks(pitch)
+      .flat_map(|line| &line[..row])
+      .copied()
+      .collect();
+    Ok((Vec2::new(w, h), pixels))
+  }
+  /// Save the frame drawn by a headless renderer to `path` as a PNG
+  pub fn save_frame(&self, path: &Path) -> Result<(), String> {
+    let (size, mut pixels) = self.frame()?;
+    save_png(&mut pixels, size, path)
+  }
+
+  /// Save the next frame to `path` as a PNG
+  pub fn request_screenshot(&mut self, path: PathBuf) {
+    self.screenshot = Some(path);
+  }
+  /// Take the path the next frame is to be saved to, if a screenshot was requested
+  pub fn take_screenshot_request(&mut self) -> Option<PathBuf> {
+    self.screenshot.take()
+  }
+
+  /// Present what has been rendered to the screen, then clear it for the next frame
+  ///
+  /// A headless renderer keeps the frame so it can be read back with `frame` or `save_frame`, so call `clear` before
+  /// drawing the next.
   pub fn present(&mut self) {
-    self.subsystem.present();
-    self.clear();
+    with_canvas!(&mut self.subsystem, canvas => canvas.present());
+    if let Canvas::Window(_) = self.subsystem {
+      self.clear();
+    }
   }
 
   /// Draw `texture` to the screen at `position`
   pub fn draw_texture<T: IntConvertable>(&mut self, texture: &Rc<Texture>, position: Vec2<T>) {
+    self.draw_texture_tinted(texture, position, UNTINTED);
+  }
+  /// Draw `texture` to the screen at `position`, with its colors multiplied by `tint` and faded by its alpha
+  pub fn draw_texture_tinted<T: IntConvertable>(&mut self, texture: &Rc<Texture>, position: Vec2<T>, tint: RGBA) {
     let (x, y) = position.destructure();
     let (w, h) = texture.dimensions.destructure();
     let src = Rect::new(0, 0, w, h);
     let dest = Rect::new(x.into(), y.into(), w, h);
-    self.subsystem.copy(&texture.internal, src, dest)
-      .map_err(|error| eprintln!("{error}"))
-      .ok();
+    self.copy(texture, src, dest, tint);
   }
   /// Draw `from` `texture` to the screen at `position`
   pub fn draw_from_texture<T: IntConvertable>(&mut self, texture: &Rc<Texture>, position: Vec2<T>, from: SrcRect) {
+    self.draw_from_texture_tinted(texture, position, from, UNTINTED);
+  }
+  /// Draw `from` `texture` to the screen at `position`, with its colors multiplied by `tint` and faded by its alpha
+  pub fn draw_from_texture_tinted<T: IntConvertable>(&mut self, texture: &Rc<Texture>, position: Vec2<T>, from: SrcRect, tint: RGBA) {
     let (x, y) = position.destructure();
     let ((sx, sy), (w, h)) = from.destructure();
     let dest = Rect::new(x.into(), y.into(), w, h);
     let src = Rect::new(sx as i32, sy as i32, w, h);
-    self.subsystem.copy(&texture.internal, src, dest)
+    self.copy(texture, src, dest, tint);
+  }
+  /// Draw `texture` to the screen at `position`, softened by averaging copies offset up to `radius` pixels each way
+  pub fn draw_texture_blurred(&mut self, texture: &Rc<Texture>, position: Vec2<i32>, radius: i32) {
+    let mut copies = 0;
+    for y in -radius..=radius {
+      for x in -radius..=radius {
+        // blending each copy by one over the copies so far keeps a running average
+        copies += 1;
+        let alpha = (UNTINTED.alpha as f32 / copies as f32).round() as u8;
+        let tint = RGBA::new(UNTINTED.red, UNTINTED.green, UNTINTED.blue, alpha);
+        self.draw_texture_tinted(texture, position + Vec2::new(x, y), tint);
+      }
+    }
+  }
+  /// Copy `src` of `texture` to `dest` on screen, modulated by `tint` and blended with the screen
+  fn copy(&mut self, texture: &Texture, src: Rect, dest: Rect, tint: RGBA) {
+    let mut internal = texture.internal.borrow_mut();
+    internal.set_blend_mode(self.blend.into());
+    let tinted = tint != UNTINTED;
+    if tinted {
+      internal.set_color_mod(tint.red, tint.green, tint.blue);
+      internal.set_alpha_mod(tint.alpha);
+    }
+
+    with_canvas!(&mut self.subsystem, canvas => canvas.copy(&internal, src, dest))
       .map_err(|error| eprintln!("{error}"))
       .ok();
+
+    // leave the texture as it was for other draws
+    if tinted {
+      internal.set_color_mod(UNTINTED.red, UNTINTED.green, UNTINTED.blue);
+      internal.set_alpha_mod(UNTINTED.alpha);
+    }
   }
+
   /// Draw `rect` of `color` to the screen
   pub fn draw_rect<T: IntConvertable, U: SizePrimitive>(&mut self, rect: Rec2<T, U>, color: RGBA) {
     self.set_color(color);
-    self.subsystem
-      .draw_rect(Rect::from(rect))
+    with_canvas!(&mut self.subsystem, canvas => canvas.draw_rect(Rect::from(rect)))
       .map_err(|error| eprintln!("{error}"))
       .ok();
   }
+  /// Fill `rect` with `color`
+  pub fn fill_rect<T: IntConvertable, U: SizePrimitive>(&mut self, rect: Rec2<T, U>, color: RGBA) {
+    self.set_color(color);
+    with_canvas!(&mut self.subsystem, canvas => canvas.fill_rect(Rect::from(rect)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+  /// Draw a line of `color` from `from` to `to`
+  pub fn draw_line<T: IntConvertable>(&mut self, from: Vec2<T>, to: Vec2<T>, color: RGBA) {
+    self.set_color(color);
+    with_canvas!(&mut self.subsystem, canvas => canvas.draw_line(Point::from(from), Point::from(to)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+
+  // shapes drawn with SDL2_gfx are alpha blended when `color` is translucent, regardless of the blend
+
+  /// Draw a circle of `color` around `center`
+  pub fn draw_circle<T: IntConvertable, U: SizePrimitive>(&mut self, center: Vec2<T>, radius: U, color: RGBA) {
+    let (x, y) = to_i16(center);
+    with_canvas!(&mut self.subsystem, canvas => canvas.circle(x, y, clamp_i16(radius.into() as i32), Color::from(&color)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+  /// Fill a circle of `color` around `center`
+  pub fn fill_circle<T: IntConvertable, U: SizePrimitive>(&mut self, center: Vec2<T>, radius: U, color: RGBA) {
+    let (x, y) = to_i16(center);
+    with_canvas!(&mut self.subsystem, canvas => canvas.filled_circle(x, y, clamp_i16(radius.into() as i32), Color::from(&color)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+  /// Draw `rect` of `color` with its corners rounded by `radius`
+  pub fn draw_rounded_rect<T: IntConvertable, U: SizePrimitive>(&mut self, rect: Rec2<T, U>, radius: U, color: RGBA) {
+    let ((x1, y1), (x2, y2)) = corners(rect);
+    with_canvas!(&mut self.subsystem, canvas => canvas.rounded_rectangle(x1, y1, x2, y2, clamp_i16(radius.into() as i32), Color::from(&color)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+  /// Fill `rect` with `color` with its corners rounded by `radius`
+  pub fn fill_rounded_rect<T: IntConvertable, U: SizePrimitive>(&mut self, rect: Rec2<T, U>, radius: U, color: RGBA) {
+    let ((x1, y1), (x2, y2)) = corners(rect);
+    with_canvas!(&mut self.subsystem, canvas => canvas.rounded_box(x1, y1, x2, y2, clamp_i16(radius.into() as i32), Color::from(&color)))
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+  }
+}
+
+/// Save `pixels` of red, green, blue, then alpha bytes, `size` pixels across, to `path` as a PNG
+fn save_png(pixels: &mut [u8], size: Vec2<u32>, path: &Path) -> Result<(), String> {
+  let pitch = size.x * FRAME_FORMAT.byte_size_per_pixel() as u32;
+  let surface = Surface::from_data(pixels, size.x, size.y, pitch, FRAME_FORMAT)?;
+  surface.save(path).map_err(|error| format!("failed to save {}: {error}", path.display()))
+}
+
+/// Clamp `value` into the coordinates SDL2_gfx draws with
+fn clamp_i16(value: i32) -> i16 {
+  value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
+
+/// Convert `position` into the coordinates SDL2_gfx draws with
+fn to_i16<T: IntConvertable>(position: Vec2<T>) -> (i16, i16) {
+  let (x, y) = position.destructure();
+  (clamp_i16(x.into()), clamp_i16(y.into()))
+}
+
+/// Get the top left and bottom right pixels of `rect` in the coordinates SDL2_gfx draws with
+fn corners<T: IntConvertable, U: SizePrimitive>(rect: Rec2<T, U>) -> ((i16, i16), (i16, i16)) {
+  let (x, y) = (rect.origin.x.into(), rect.origin.y.into());
+  let (w, h) = (rect.size.x.into() as i32, rect.size.y.into() as i32);
+  ((clamp_i16(x), clamp_i16(y)), (clamp_i16(x + w - 1), clamp_i16(y + h - 1)))
 }
 
 /// Create a new `sdl2::video::Window` with the given `RendererProperties`
@@ -135,7 +519,9 @@ fn build_window(context: &sdl2::Sdl, properties: &Properties) -> Result<sdl2::vi
   let video_subsystem = context.video()?;
 
   let mut builder = video_subsystem.window(properties.title.as_str(), w, h);
+  if let Some(position) = properties.position { builder.position(position.x, position.y); };
   if properties.fullscreen { builder.fullscreen_desktop(); };
+  if properties.resizable { builder.resizable(); };
   if properties.opengl { builder.opengl(); };
 
   let window = builder.build().map_err(|e| e.to_string())?;
@@ -0,0 +1,175 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::engine::asset::texture::SrcRect;
+use crate::engine::geometry::{Rec2, Vec2};
+use crate::engine::render::color::RGBA;
+use crate::engine::render::Renderer;
+use crate::engine::tile::tile::TileId;
+use crate::engine::tile::tileset::Tileset;
+use crate::engine::tween::Tweenable;
+use crate::engine::utility::random::random;
+use crate::engine::utility::types::Size;
+
+/**
+ * Particles cut from a tileset, spawned by emitters, that move, fall, and fade over their lifetime
+ */
+
+/// The longest step particles are moved in one update, so they don't leap after a pause
+const MAX_STEP: Duration = Duration::from_millis(100);
+
+/// How an emitter spawns particles and how they move
+#[derive(Clone, Copy, Debug)]
+pub struct EmitterSettings {
+  /// particles spawned at once when the emitter starts
+  pub burst: u32,
+  /// particles spawned each second while the emitter runs
+  pub rate: f32,
+  /// how long the emitter runs for
+  pub duration: Duration,
+  /// how long each particle lives
+  pub lifetime: Duration,
+  /// the least and most velocity a particle starts with, in pixels per second
+  pub velocity: (Vec2<f32>, Vec2<f32>),
+  /// downward acceleration in pixels per second per second
+  pub gravity: f32,
+  /// tint of a particle when it spawns and when it dies, blended between over its life
+  pub colors: (RGBA, RGBA),
+  /// width and height of the square cut from the tile for each particle
+  pub size: Size,
+}
+
+/// Spawns particles over an area for the duration of its settings
+#[derive(Debug)]
+struct Emitter {
+  settings: EmitterSettings,
+  area: Rec2<i32, Size>,
+  tile_id: TileId,
+  age: Duration,
+  /// particles owed to the rate that haven't spawned yet
+  owed: f32,
+}
+
+/// A single particle
+#[derive(Debug)]
+struct Particle {
+  position: Vec2<f32>,
+  velocity: Vec2<f32>,
+  gravity: f32,
+  age: Duration,
+  lifetime: Duration,
+  colors: (RGBA, RGBA),
+  src: SrcRect,
+}
+
+/// Updates and draws the particles of emitters that use tiles from a tileset
+pub struct ParticleSystem {
+  tileset: Rc<Tileset>,
+  emitters: Vec<Emitter>,
+  particles: Vec<Particle>,
+  last_update: Instant,
+}
+
+impl ParticleSystem {
+  /// Instantiate a new particle system drawing particles from `tileset`
+  pub fn new(tileset: Rc<Tileset>) -> Self {
+    Self {
+      tileset,
+      emitters: Vec::new(),
+      particles: Vec::new(),
+      last_update: Instant::now(),
+    }
+  }
+
+  /// Start spawning particles cut from `tile_id` over `area` as described by `settings`
+  pub fn emit(&mut self, settings: &EmitterSettings, area: Rec2<i32, Size>, tile_id: TileId) {
+    let mut emitter = Emitter { settings: *settings, area, tile_id, age: Duration::ZERO, owed: 0.0 };
+    for _ in 0..settings.burst {
+      self.spawn(&emitter);
+    }
+    emitter.owed = 0.0;
+    if !settings.duration.is_zero() {
+      self.emitters.push(emitter);
+    }
+  }
+
+  /// Remove every emitter and particle
+  pub fn clear(&mut self) {
+    self.emitters.clear();
+    self.particles.clear();
+  }
+
+  /// Spawn owed particles, then move and age every particle by the time since the last update
+  pub fn update(&mut self) {
+    let step = self.last_update.elapsed().min(MAX_STEP);
+    self.last_update = Instant::now();
+    let seconds = step.as_secs_f32();
+
+    // spawn particles
+    let mut emitters = std::mem::take(&mut self.emitters);
+    for emitter in &mut emitters {
+      emitter.age += step;
+      emitter.owed += emitter.settings.rate * seconds;
+      while emitter.owed >= 1.0 {
+        self.spawn(emitter);
+        emitter.owed -= 1.0;
+      }
+    }
+    emitters.retain(|emitter| emitter.age < emitter.settings.duration);
+    self.emitters = emitters;
+
+    // move particles
+    for particle in &mut self.particles {
+      particle.age += step;
+      particle.velocity.y += particle.gravity * seconds;
+      particle.position = particle.position + Vec2::new(particle.velocity.x * seconds, particle.velocity.y * seconds);
+    }
+    self.particles.retain(|particle| particle.age < particle.lifetime);
+  }
+
+  /// Draw every particle
+  pub fn render(&self, renderer: &mut Renderer) {
+    for particle in &self.particles {
+      let life = particle.age.as_secs_f32() / particle.lifetime.as_secs_f32();
+      let (from, to) = particle.colors;
+      let position = Vec2::new(particle.position.x.round() as i32, particle.position.y.round() as i32);
+      renderer.draw_from_texture_tinted(&self.tileset.texture, position, particle.src, from.lerp(to, life));
+    }
+  }
+
+  /// Add a particle at a random point of the area of `emitter`
+  fn spawn(&mut self, emitter: &Emitter) {
+    let Some(tile) = self.tileset.get_tiledata(emitter.tile_id) else {
+      return;
+    };
+    let settings = &emitter.settings;
+
+    // cut a square from somewhere on the tile
+    let size = settings.size.min(tile.src.size.x).min(tile.src.size.y);
+    let cut = Vec2::new(random_between(0, tile.src.size.x - size), random_between(0, tile.src.size.y - size));
+    let src = Rec2::new(tile.src.origin + cut, Vec2::new(size, size));
+
+    let area = emitter.area;
+    let position = Vec2::new(
+      area.origin.x as f32 + random_between(0.0, area.size.x as f32),
+      area.origin.y as f32 + random_between(0.0, area.size.y as f32),
+    );
+    let (least, most) = settings.velocity;
+    let velocity = Vec2::new(random_between(least.x, most.x), random_between(least.y, most.y));
+
+    self.particles.push(Particle {
+      position,
+      velocity,
+      gravity: settings.gravity,
+      age: Duration::ZERO,
+      lifetime: settings.lifetime,
+      colors: settings.colors,
+      src,
+    });
+  }
+}
+
+/// Get a random number from `from` up to `to`, or `from` if the range is empty
+fn random_between<T: rand::distributions::uniform::SampleUniform + PartialOrd + Copy>(from: T, to: T) -> T {
+  if from < to { random(from, to) } else { from }
+}
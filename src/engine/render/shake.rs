@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use crate::engine::geometry::Vec2;
+use crate::engine::tween::{Easing, Tween};
+use crate::engine::utility::random::random;
+
+/**
+ * A jolt that jitters whatever it offsets and dies away
+ */
+
+/// Jitters an offset by an amplitude that decays to rest
+#[derive(Debug)]
+pub struct Shake {
+  amplitude: Tween<f32>,
+}
+
+impl Shake {
+  /// Instantiate a shake at rest
+  pub fn new() -> Self {
+    Self { amplitude: Tween::at(0.0) }
+  }
+
+  /// Shake up to `intensity` pixels, decaying over `duration`, unless a stronger shake is already going
+  pub fn start(&mut self, intensity: f32, duration: Duration) {
+    if intensity > self.amplitude.value() {
+      self.amplitude = Tween::new(intensity, 0.0, duration, Easing::QuadOut);
+    }
+  }
+
+  /// Stop shaking
+  pub fn stop(&mut self) {
+    self.amplitude = Tween::at(0.0);
+  }
+
+  /// Get a random offset within the current amplitude
+  pub fn offset(&mut self) -> Vec2<i32> {
+    let amplitude = self.amplitude.update();
+    if amplitude < 0.5 {
+      return Vec2::default();
+    }
+    let jitter = || random(-amplitude, amplitude).round() as i32;
+    Vec2::new(jitter(), jitter())
+  }
+}
@@ -45,8 +45,14 @@ impl Text {
 
   /// Sets the text content of the text and
   pub fn set_content(&mut self, content: String) {
-    self.content = content;
-    self.dirty = true;
+    if content != self.content {
+      self.content = content;
+      self.dirty = true;
+    }
+  }
+  /// Move the text to `position`
+  pub fn set_position(&mut self, position: Vec2<i32>) {
+    self.position = position;
   }
   /// Clears the text content of the text
   pub fn clear_content(&mut self) {
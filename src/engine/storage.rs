@@ -0,0 +1,27 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/**
+ * Read and write data files
+ */
+
+/// Get the path of `file` in the directory for data the user writes, creating the directory if needed
+pub fn user_path(organization: &str, application: &str, file: &str) -> Result<PathBuf, String> {
+  let directory = sdl2::filesystem::pref_path(organization, application).map_err(|error| error.to_string())?;
+  Ok(PathBuf::from(directory).join(file))
+}
+
+/// Read `path` as JSON
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+  let json = fs::read_to_string(path).map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+  serde_json::from_str(&json).map_err(|error| format!("failed to parse {}: {error}", path.display()))
+}
+
+/// Write `value` to `path` as JSON
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+  let json = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+  fs::write(path, json).map_err(|error| format!("failed to write {}: {error}", path.display()))
+}
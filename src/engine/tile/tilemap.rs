@@ -79,7 +79,7 @@ impl Tilemap {
   }
 
   /// Convert `coordinate` to worldspace
-  fn coord_to_worldspace(&self, coordinate: &Coordinate) -> Vec2<i32> {
+  pub fn coord_to_worldspace(&self, coordinate: &Coordinate) -> Vec2<i32> {
     let (tile_width, tile_height) = self.tileset.tile_size.destructure();
     Vec2::new(
       self.position.x + (coordinate.x * tile_width as i32),
@@ -87,6 +87,16 @@ impl Tilemap {
     )
   }
 
+  /// Convert `position` in worldspace to the coordinate of the tile there, if it is within the tilemap
+  pub fn worldspace_to_coord(&self, position: Vec2<i32>) -> Option<Coordinate> {
+    let (tile_width, tile_height) = self.tileset.tile_size.destructure();
+    let coordinate = Coordinate::new(
+      (position.x - self.position.x).div_euclid(tile_width as i32),
+      (position.y - self.position.y).div_euclid(tile_height as i32),
+    );
+    self.is_bound(&coordinate).then_some(coordinate)
+  }
+
   /// Check if `coordinate` is within the bounds of the tilemap
   pub fn is_bound(&self, coordinate: &Coordinate) -> bool {
     let x_bound = coordinate.x >= 0 && coordinate.x < self.dimensions.x as i32;
@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /**
  * Time utilities
@@ -38,11 +38,40 @@ impl Timer {
     self.start = Instant::now();
     self.enabled = true;
   }
+  /// Stop the timer, it will not be done until started again
+  pub fn stop(&mut self) {
+    self.enabled = false;
+  }
   /// set the start time to now
   pub fn restart(&mut self) {
     self.start = Instant::now();
   }
 
+  /// Get the time left until the timer is done, or `None` if it is disabled
+  pub fn remaining(&self) -> Option<Duration> {
+    self.enabled.then(|| self.duration.saturating_sub(self.start.elapsed()))
+  }
+  /// Get how far through its duration the timer is, from 0 to 1
+  pub fn progress(&self) -> f32 {
+    if self.duration.is_zero() {
+      return 1.0;
+    }
+    (self.start.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+  }
+  /// Start the timer so it is done after `remaining`, or stop it if `None`
+  pub fn resume(&mut self, remaining: Option<Duration>) {
+    let Some(remaining) = remaining else {
+      self.stop();
+      return;
+    };
+
+    self.start();
+    let elapsed = self.duration.saturating_sub(remaining);
+    if let Some(start) = self.start.checked_sub(elapsed) {
+      self.start = start;
+    }
+  }
+
   /// Check if the timer has expired then perform `action`
   pub fn consume(&mut self, action: ConsumeAction) -> bool {
     if !self.enabled {
@@ -69,3 +98,22 @@ impl Timer {
     done
   }
 }
+
+/// Get the current date and time in UTC as `YYYYMMDD-HHMMSS`, for naming files
+pub fn timestamp() -> String {
+  let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+  let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+  // convert days since the epoch to a civil date
+  let era_days = days + 719_468;
+  let era = era_days.div_euclid(146_097);
+  let day_of_era = era_days.rem_euclid(146_097);
+  let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153; // months from march
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+  let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+  format!("{year:04}{month:02}{day:02}-{:02}{:02}{:02}", time / 3_600, time % 3_600 / 60, time % 60)
+}
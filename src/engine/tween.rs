@@ -0,0 +1,288 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::engine::geometry::{UnitPrimitive, Vec2};
+use crate::engine::render::color::RGBA;
+use crate::engine::time::Timer;
+
+/**
+ * Values eased from one to another over time, for positions, colors, alpha, scale, and counters
+ */
+
+// Easing //
+
+/// Curves that shape how a tween moves between its values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+  Linear,
+  QuadIn,
+  QuadOut,
+  QuadInOut,
+  CubicIn,
+  CubicOut,
+  CubicInOut,
+  SineIn,
+  SineOut,
+  SineInOut,
+  /// overshoots the end then settles back
+  BackOut,
+  /// bounces off the end like a dropped ball
+  BounceOut,
+  /// springs past the end and wobbles to rest
+  ElasticOut,
+}
+
+impl Easing {
+  /// Ease `t` from 0 to 1 along the curve
+  pub fn apply(&self, t: f32) -> f32 {
+    use std::f32::consts::PI;
+
+    let t = t.clamp(0.0, 1.0);
+    match self {
+      Easing::Linear => t,
+      Easing::QuadIn => t * t,
+      Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+      Easing::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+      Easing::CubicIn => t.powi(3),
+      Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+      Easing::CubicInOut => if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+      Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+      Easing::SineOut => (t * PI / 2.0).sin(),
+      Easing::SineInOut => -((t * PI).cos() - 1.0) / 2.0,
+      Easing::BackOut => {
+        let overshoot = 1.70158;
+        1.0 + (overshoot + 1.0) * (t - 1.0).powi(3) + overshoot * (t - 1.0).powi(2)
+      }
+      Easing::BounceOut => bounce_out(t),
+      Easing::ElasticOut => {
+        if t == 0.0 || t == 1.0 {
+          return t;
+        }
+        2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+      }
+    }
+  }
+}
+
+fn bounce_out(t: f32) -> f32 {
+  let (n, d) = (7.5625, 2.75);
+  if t < 1.0 / d {
+    n * t * t
+  } else if t < 2.0 / d {
+    let t = t - 1.5 / d;
+    n * t * t + 0.75
+  } else if t < 2.5 / d {
+    let t = t - 2.25 / d;
+    n * t * t + 0.9375
+  } else {
+    let t = t - 2.625 / d;
+    n * t * t + 0.984375
+  }
+}
+
+// Tweenable //
+
+/// A value that can be blended between two others
+pub trait Tweenable: Copy {
+  /// Blend from `self` toward `to` by `amount`, where 0 is `self` and 1 is `to`
+  ///
+  /// `amount` may leave 0 to 1 for curves that overshoot
+  fn lerp(self, to: Self, amount: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    self + (to - self) * amount
+  }
+}
+
+impl Tweenable for i32 {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    (self as f32).lerp(to as f32, amount).round() as i32
+  }
+}
+
+impl Tweenable for u32 {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    (self as f64 + (to as f64 - self as f64) * amount as f64).round().max(0.0) as u32
+  }
+}
+
+impl Tweenable for u8 {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    (self as f32).lerp(to as f32, amount).round().clamp(0.0, u8::MAX as f32) as u8
+  }
+}
+
+impl<T: Tweenable + UnitPrimitive> Tweenable for Vec2<T> {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    Vec2::new(self.x.lerp(to.x, amount), self.y.lerp(to.y, amount))
+  }
+}
+
+impl Tweenable for RGBA {
+  fn lerp(self, to: Self, amount: f32) -> Self {
+    let (red, green, blue, alpha) = self.destructure();
+    RGBA::new(red.lerp(to.red, amount), green.lerp(to.green, amount), blue.lerp(to.blue, amount), alpha.lerp(to.alpha, amount))
+  }
+}
+
+// Tween //
+
+/// A leg of a tween, easing toward `to` over `duration`
+#[derive(Clone, Copy, Debug)]
+struct Step<T> {
+  to: T,
+  duration: Duration,
+  easing: Easing,
+}
+
+/// Eases a value through a chain of steps, each starting where the last ended
+#[derive(Debug)]
+pub struct Tween<T: Tweenable> {
+  /// value the current step starts from
+  from: T,
+  steps: VecDeque<Step<T>>,
+  timer: Timer,
+  /// steps finished since the tween began
+  finished: usize,
+}
+
+impl<T: Tweenable> Tween<T> {
+  /// Begin easing from `from` to `to` over `duration`
+  pub fn new(from: T, to: T, duration: Duration, easing: Easing) -> Self {
+    Self {
+      from,
+      steps: VecDeque::from([Step { to, duration, easing }]),
+      timer: Timer::new(duration, true),
+      finished: 0,
+    }
+  }
+
+  /// A tween resting at `value`
+  pub fn at(value: T) -> Self {
+    Self { from: value, steps: VecDeque::new(), timer: Timer::new(Duration::ZERO, false), finished: 0 }
+  }
+
+  /// Ease on to `to` over `duration` once the steps before are finished
+  pub fn then(mut self, to: T, duration: Duration, easing: Easing) -> Self {
+    if self.steps.is_empty() {
+      self.timer = Timer::new(duration, true);
+    }
+    self.steps.push_back(Step { to, duration, easing });
+    self
+  }
+
+  /// Ease from the current value to `to` over `duration`, dropping any steps left
+  pub fn retarget(&mut self, to: T, duration: Duration, easing: Easing) {
+    *self = Self::new(self.value(), to, duration, easing);
+  }
+
+  /// Get the value at this point of the tween
+  pub fn value(&self) -> T {
+    match self.steps.front() {
+      Some(step) => self.from.lerp(step.to, step.easing.apply(self.timer.progress())),
+      None => self.from,
+    }
+  }
+
+  /// Get the value the tween ends at
+  pub fn target(&self) -> T {
+    self.steps.back().map_or(self.from, |step| step.to)
+  }
+
+  /// Check if every step has finished
+  pub fn is_done(&self) -> bool {
+    self.steps.is_empty()
+  }
+
+  /// Move past the steps that have finished
+  ///
+  /// returns the value at this point of the tween
+  pub fn update(&mut self) -> T {
+    self.update_map(&mut |_| {})
+  }
+
+  /// Move past the steps that have finished, invoking `callback` with the index of each one
+  ///
+  /// returns the value at this point of the tween
+  pub fn update_map(&mut self, callback: &mut dyn FnMut(usize)) -> T {
+    while self.timer.done() {
+      let Some(step) = self.steps.pop_front() else {
+        break;
+      };
+      self.from = step.to;
+      (callback)(self.finished);
+      self.finished += 1;
+      match self.steps.front() {
+        Some(next) => self.timer = Timer::new(next.duration, true),
+        None => self.timer.stop(),
+      }
+    }
+    self.value()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Long enough that a step barely moves while a test runs
+  const HOUR: Duration = Duration::from_secs(60 * 60);
+
+  fn assert_near(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 0.01, "{actual} is not near {expected}");
+  }
+
+  #[test]
+  fn eases_every_curve_from_0_to_1() {
+    let curves = [
+      Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut,
+      Easing::CubicInOut, Easing::SineIn, Easing::SineOut, Easing::SineInOut, Easing::BackOut, Easing::BounceOut,
+      Easing::ElasticOut,
+    ];
+    for easing in curves {
+      assert!(easing.apply(0.0).abs() < 1e-5, "{easing:?} starts at {}", easing.apply(0.0));
+      assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{easing:?} ends at {}", easing.apply(1.0));
+    }
+  }
+
+  #[test]
+  fn chains_steps_from_where_the_last_ended() {
+    let mut tween = Tween::new(0.0, 10.0, Duration::ZERO, Easing::Linear).then(20.0, HOUR, Easing::Linear);
+    assert_eq!(tween.target(), 20.0);
+
+    assert_near(tween.update(), 10.0);
+    assert!(!tween.is_done());
+    assert_eq!(tween.target(), 20.0);
+
+    let mut resting = Tween::at(5.0).then(15.0, HOUR, Easing::Linear);
+    assert_near(resting.update(), 5.0);
+    assert_eq!(resting.target(), 15.0);
+  }
+
+  #[test]
+  fn calls_back_once_per_finished_step_in_order() {
+    let mut tween = Tween::new(0, 1, Duration::ZERO, Easing::Linear)
+      .then(2, Duration::ZERO, Easing::Linear)
+      .then(3, Duration::ZERO, Easing::Linear)
+      .then(4, HOUR, Easing::Linear);
+
+    let mut finished = Vec::new();
+    assert_eq!(tween.update_map(&mut |index| finished.push(index)), 3);
+    assert_eq!(finished, vec![0, 1, 2]);
+
+    tween.update_map(&mut |index| finished.push(index));
+    assert_eq!(finished, vec![0, 1, 2]); // the last step is still running
+  }
+
+  #[test]
+  fn retargets_from_the_current_value() {
+    let mut tween = Tween::new(0.0, 10.0, Duration::ZERO, Easing::Linear).then(20.0, HOUR, Easing::Linear);
+    tween.update();
+
+    tween.retarget(-5.0, HOUR, Easing::Linear);
+    assert_near(tween.value(), 10.0);
+    assert_eq!(tween.target(), -5.0);
+  }
+}
@@ -1,5 +1,8 @@
 use rand::distributions::uniform::SampleUniform;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 
 /**
  * Random utilities
@@ -8,4 +11,29 @@ use rand::Rng;
 /// Generate a random number between `from` and `to`.
 pub fn random<T>(from: T, to: T) -> T where T: SampleUniform + PartialOrd {
   rand::thread_rng().gen_range(from..to)
-}
\ No newline at end of file
+}
+
+/// Put `items` in a random order
+pub fn shuffle<T>(items: &mut [T]) {
+  items.shuffle(&mut rand::thread_rng());
+}
+
+/// Generates the same sequence of random numbers for the same seed
+///
+/// The generator can be serialized to continue the sequence where it left off.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeededRandom {
+  rng: ChaCha12Rng,
+}
+
+impl SeededRandom {
+  /// Instantiate a new generator from `seed`
+  pub fn new(seed: u64) -> Self {
+    Self { rng: ChaCha12Rng::seed_from_u64(seed) }
+  }
+
+  /// Generate the next random number between `from` and `to`
+  pub fn range<T>(&mut self, from: T, to: T) -> T where T: SampleUniform + PartialOrd {
+    self.rng.gen_range(from..to)
+  }
+}
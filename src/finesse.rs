@@ -0,0 +1,246 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::ai::field::Field;
+use crate::ai::search::{Pose, step};
+use crate::algorithm::transform_shape;
+use crate::board::Action;
+use crate::constants::piece::PieceData;
+use crate::controls::Controls;
+use crate::engine::event::EventStore;
+use crate::engine::utility::types::{Coordinate, Size2};
+
+/**
+ * Compare the inputs that placed a piece against the fewest that could have
+ */
+
+/// Inputs that place a piece, holding a direction slides the piece to the wall as one input
+const INPUTS: [Action; 3] = [Action::MoveLeft, Action::MoveRight, Action::Rotate];
+
+/// Find the fewest inputs that move `piece` from `start` to where it drops onto the cells `target` drops onto
+///
+/// Finesse ignores the stack, so inputs are counted on an empty board of `size`. Rotations that cover the same cells,
+/// like an O that turns, reach the same placement.
+pub fn minimal_inputs(size: Size2, piece: &PieceData, start: Pose, target: Pose) -> Option<u32> {
+  let field = Field::new(size);
+  let target_cells = dropped_cells(&field, piece, &target);
+  let mut visited = HashSet::from([start]);
+  let mut queue = VecDeque::from([(start, 0)]);
+  while let Some((pose, inputs)) = queue.pop_front() {
+    if dropped_cells(&field, piece, &pose) == target_cells {
+      return Some(inputs);
+    }
+
+    for action in INPUTS {
+      let Some(tapped) = step(&field, piece, &pose, action) else {
+        continue;
+      };
+
+      let mut reached = vec![tapped];
+      if action != Action::Rotate {
+        let mut held = tapped;
+        while let Some(next) = step(&field, piece, &held, action) {
+          held = next;
+        }
+        reached.push(held);
+      }
+
+      for next in reached {
+        if visited.insert(next) {
+          queue.push_back((next, inputs + 1));
+        }
+      }
+    }
+  }
+
+  None
+}
+
+/// Get the cells `piece` covers at `pose` once hard dropped onto the floor of the empty `field`
+fn dropped_cells(field: &Field, piece: &PieceData, pose: &Pose) -> HashSet<Coordinate> {
+  let dropped = step(field, piece, pose, Action::HardDrop).unwrap_or(*pose);
+  transform_shape(&piece.shape[dropped.rotation], &dropped.position).into_iter().collect()
+}
+
+/// Counts the inputs used to place each piece, and the pieces placed with more than needed
+#[derive(Debug, Default)]
+pub struct Finesse {
+  /// where the active piece spawned
+  start: Option<Pose>,
+  inputs: u32,
+  /// soft dropping allows tucks that finesse can't judge
+  soft_dropped: bool,
+  pub faults: u32,
+}
+
+impl Finesse {
+  /// Instantiate a new tracker without faults
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Begin counting inputs for a piece that spawned at `start`
+  pub fn begin(&mut self, start: Pose) {
+    self.start = Some(start);
+    self.inputs = 0;
+    self.soft_dropped = false;
+  }
+
+  /// Count the keys of `controls` pressed this frame
+  pub fn record(&mut self, controls: &Controls, events: &EventStore) {
+    for key in [controls.left, controls.right, controls.rotate] {
+      if events.is_key_pressed(key) {
+        self.inputs += 1;
+      }
+    }
+    self.soft_dropped |= events.is_key_pressed(controls.down);
+  }
+
+  /// Judge the inputs that placed `piece` at `landing` on a board of `size`
+  ///
+  /// returns `true` and counts a fault if more inputs were used than needed
+  pub fn judge(&mut self, size: Size2, piece: &PieceData, landing: Pose) -> bool {
+    let Some(start) = self.start.take() else {
+      return false;
+    };
+    if self.soft_dropped {
+      return false;
+    }
+
+    let fault = minimal_inputs(size, piece, start, landing).is_some_and(|minimal| self.inputs > minimal);
+    self.count(fault)
+  }
+
+  /// Judge a drill, where `piece` must reach `target` on a board of `size` with the fewest inputs and no soft drop
+  ///
+  /// returns `true` and counts a fault if the piece landed at `landing` any other way
+  pub fn judge_drill(&mut self, size: Size2, piece: &PieceData, landing: Pose, target: Pose) -> bool {
+    let Some(start) = self.start.take() else {
+      return false;
+    };
+
+    let minimal = minimal_inputs(size, piece, start, target);
+    let fault = landing != target || self.soft_dropped || minimal.is_some_and(|minimal| self.inputs > minimal);
+    self.count(fault)
+  }
+
+  fn count(&mut self, fault: bool) -> bool {
+    if fault {
+      self.faults += 1;
+    }
+    fault
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::constants::controls::PLAYER_ONE_CONTROLS;
+  use crate::constants::piece::{parse_shape, ShapeType};
+  use crate::engine::geometry::Vec2;
+
+  const SIZE: Size2 = Vec2::new(10, 20);
+
+  fn inputs_to(shape_type: ShapeType, start: Pose, target: Pose) -> Option<u32> {
+    minimal_inputs(SIZE, shape_type.data(), start, target)
+  }
+
+  #[test]
+  fn counts_taps() {
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    assert_eq!(inputs_to(ShapeType::O, start, start), Some(0));
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(3, 10))), Some(1));
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(6, 10))), Some(2));
+  }
+
+  #[test]
+  fn counts_holding_to_the_wall_as_one_input() {
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(0, 10))), Some(1));
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(8, 10))), Some(1));
+    // hold to the wall, then tap back
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(1, 10))), Some(2));
+  }
+
+  #[test]
+  fn counts_rotations() {
+    let start = Pose::new(0, Coordinate::new(3, 0));
+    assert_eq!(inputs_to(ShapeType::T, start, Pose::new(1, Coordinate::new(3, 10))), Some(1));
+    assert_eq!(inputs_to(ShapeType::T, start, Pose::new(2, Coordinate::new(3, 10))), Some(2));
+    // upright against the left wall, where the empty column of its box overhangs the wall
+    assert_eq!(inputs_to(ShapeType::T, start, Pose::new(1, Coordinate::new(-1, 10))), Some(2));
+  }
+
+  /// Build a piece of `rotations`, written as rows the way piece sets are
+  fn piece(rotations: &[&[&str]]) -> PieceData {
+    PieceData::new("TEST", rotations.iter().map(|rows| parse_shape(rows)).collect(), 1, Coordinate::default())
+  }
+
+  /// Build an O that turns through four rotations, each covering the same cells
+  fn turning_o() -> PieceData {
+    let rows: &[&str] = &["##", "##"];
+    piece(&[rows; 4])
+  }
+
+  #[test]
+  fn counts_no_rotations_to_turns_covering_the_same_cells() {
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    for rotation in 1..4 {
+      assert_eq!(minimal_inputs(SIZE, &turning_o(), start, Pose::new(rotation, Coordinate::new(4, 10))), Some(0), "{rotation}");
+    }
+
+    // upside down, the I covers the same cells a row lower in its box
+    let i = piece(&[&["    ", "####", "    ", "    "], &["  # "; 4], &["    ", "    ", "####", "    "], &[" #  "; 4]]);
+    let start = Pose::new(0, Coordinate::new(3, 0));
+    assert_eq!(minimal_inputs(SIZE, &i, start, Pose::new(2, Coordinate::new(3, 10))), Some(0));
+    assert_eq!(minimal_inputs(SIZE, &i, start, Pose::new(3, Coordinate::new(3, 10))), Some(2));
+  }
+
+  #[test]
+  fn finds_no_inputs_to_an_unreachable_column() {
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    assert_eq!(inputs_to(ShapeType::O, start, Pose::new(0, Coordinate::new(9, 10))), None);
+  }
+
+  /// Judge an O piece tapped left `taps` times from the middle of the board, landing `taps` columns left
+  fn judge_taps(taps: i32) -> bool {
+    let controls = PLAYER_ONE_CONTROLS;
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    let mut finesse = Finesse::new();
+    finesse.begin(start);
+
+    let mut events = EventStore::new();
+    for _ in 0..taps {
+      events.press_key(controls.left);
+      finesse.record(&controls, &events);
+      events.raise_key(controls.left);
+      events.clear_pressed_keys();
+    }
+
+    finesse.judge(SIZE, ShapeType::O.data(), Pose::new(0, Coordinate::new(4 - taps, 18)))
+  }
+
+  #[test]
+  fn judges_turning_an_o_as_a_fault() {
+    let controls = PLAYER_ONE_CONTROLS;
+    let start = Pose::new(0, Coordinate::new(4, 0));
+    let mut finesse = Finesse::new();
+    finesse.begin(start);
+
+    let mut events = EventStore::new();
+    for _ in 0..2 {
+      events.press_key(controls.rotate);
+      finesse.record(&controls, &events);
+      events.raise_key(controls.rotate);
+      events.clear_pressed_keys();
+    }
+
+    assert!(finesse.judge(SIZE, &turning_o(), Pose::new(2, Coordinate::new(4, 18))));
+  }
+
+  #[test]
+  fn judges_extra_inputs_as_a_fault() {
+    assert!(!judge_taps(1));
+    assert!(!judge_taps(2)); // tapping twice is as few as holding to the wall and tapping back
+    assert!(judge_taps(4)); // holding to the wall takes one
+  }
+}
@@ -0,0 +1,681 @@
+use crate::ai::search::Pose;
+use crate::constants::game::{GARBAGE_TILE_ID, MATRIX_DIMENSIONS};
+use crate::constants::piece::ShapeType;
+use crate::engine::tile::tile::TileId;
+use crate::engine::utility::types::Coordinate;
+use crate::tbp::{location_to_pose, pose_to_location};
+use crate::tbp::message::{Orientation, PieceLocation};
+
+/**
+ * Fumen v115, the format boards are shared in, read from and written to our boards
+ */
+
+/// Marks the start of the data
+const PREFIX: &str = "v115@";
+/// Digits of the base 64 numbers the data is written in, least significant first
+const DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Digits before the first `?` separator, and between each after it
+const FIRST_CHUNK: usize = 42;
+const CHUNK: usize = 47;
+
+/// Rows of a field above its garbage row
+const FIELD_TOP: usize = 23;
+const FIELD_WIDTH: usize = 10;
+/// Cells of a field, including the garbage row
+const FIELD_CELLS: usize = (FIELD_TOP + 1) * FIELD_WIDTH;
+/// Difference written for a cell that is the same as the previous page
+const NO_CHANGE: u32 = 8;
+/// Most following pages that can share an unchanged field
+const MAX_REPEAT: u32 = 63;
+
+/// Codes of the cells of a field
+const EMPTY: u8 = 0;
+const GARBAGE: u8 = 8;
+/// Pieces in the order of their codes, from 1
+const PIECES: [ShapeType; 7] = [ShapeType::I, ShapeType::L, ShapeType::O, ShapeType::Z, ShapeType::T, ShapeType::J, ShapeType::S];
+/// Orientations in the order of their codes
+const ORIENTATIONS: [Orientation; 4] = [Orientation::South, Orientation::East, Orientation::North, Orientation::West];
+
+/// Comments are escaped and written in groups of characters from ` ` to `~`
+const COMMENT_FIRST: u32 = ' ' as u32;
+const COMMENT_BASE: u32 = 96;
+const COMMENT_GROUP: usize = 4;
+const MAX_COMMENT: usize = 4095;
+/// Characters JavaScript's `escape` leaves as they are
+const UNESCAPED: &str = "@*_+-./";
+
+/// Cell codes of a field, top down, with the garbage row last
+type Cells = [u8; FIELD_CELLS];
+
+/// A page of a fumen in the coordinates of our board
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Page {
+  /// landed tiles and the tile filling them
+  pub cells: Vec<(Coordinate, TileId)>,
+  /// the active piece
+  pub piece: Option<(ShapeType, Pose)>,
+  pub comment: String,
+  /// the piece locks into the field and full lines clear before the next page
+  pub lock: bool,
+}
+
+/// Read every page of the fumen in `data`, which can be a link to a fumen
+pub fn decode(data: &str) -> Result<Vec<Page>, String> {
+  let start = data.find(PREFIX).ok_or("no fumen v115 data found")?;
+  let mut reader = Reader::new(&data[start + PREFIX.len()..])?;
+
+  let mut pages = Vec::new();
+  let mut field: Cells = [EMPTY; FIELD_CELLS];
+  let mut comment = String::new();
+  let mut repeat = 0;
+  while !reader.is_empty() {
+    let mut cells = field;
+    if repeat > 0 {
+      repeat -= 1; // the field is unchanged
+    } else if !read_field(&mut reader, &mut cells)? {
+      repeat = reader.read(1)?;
+    }
+
+    let action = Flags::decode(reader.read(3)?);
+    if action.comment {
+      comment = read_comment(&mut reader)?;
+    }
+
+    let piece = action.piece.map(|location| location_to_pose(&location, FIELD_TOP as u32).ok_or("invalid fumen piece")).transpose()?;
+    pages.push(Page {
+      cells: to_board(&cells),
+      piece: piece.map(|(shape_type, pose)| (shape_type, Pose::new(pose.rotation, pose.position + Coordinate::new(0, -row_offset())))),
+      comment: comment.clone(),
+      lock: action.lock,
+    });
+
+    field = cells;
+    if action.lock {
+      if let Some((shape_type, pose)) = piece {
+        lock(&mut field, shape_type, &pose);
+      }
+      clear_lines(&mut field);
+      if action.rise {
+        rise(&mut field);
+      }
+      if action.mirror {
+        mirror(&mut field);
+      }
+    }
+  }
+
+  if pages.is_empty() {
+    return Err(String::from("fumen has no pages"));
+  }
+  Ok(pages)
+}
+
+/// Write `pages` as a fumen, there should be at least one
+pub fn encode(pages: &[Page]) -> String {
+  let mut digits = Vec::new();
+  let mut field: Cells = [EMPTY; FIELD_CELLS];
+  let mut comment = "";
+  let mut repeat: Option<usize> = None;
+  for (index, page) in pages.iter().enumerate() {
+    let mut cells = from_board(&page.cells);
+    if cells != field {
+      write_field(&mut digits, &field, &cells);
+      repeat = None;
+    } else {
+      match repeat {
+        Some(repeat) if digits[repeat] < MAX_REPEAT => digits[repeat] += 1,
+        _ => {
+          write_field(&mut digits, &field, &cells);
+          digits.push(0);
+          repeat = Some(digits.len() - 1);
+        }
+      }
+    }
+
+    let piece = page.piece.map(|(shape_type, pose)| (shape_type, Pose::new(pose.rotation, pose.position + Coordinate::new(0, row_offset()))));
+    let location = piece
+      .and_then(|(shape_type, pose)| pose_to_location(shape_type, &pose, FIELD_TOP as u32))
+      .filter(|location| location_index(location).is_some());
+    let comment_changed = page.comment != comment;
+    let action = Flags {
+      piece: location,
+      rise: false,
+      mirror: false,
+      colorize: index == 0,
+      comment: comment_changed,
+      lock: page.lock,
+    };
+    write(&mut digits, action.encode(), 3);
+    if comment_changed {
+      write_comment(&mut digits, &page.comment);
+      comment = &page.comment;
+    }
+
+    if page.lock {
+      if let Some((shape_type, pose)) = piece {
+        lock(&mut cells, shape_type, &pose);
+      }
+      clear_lines(&mut cells);
+    }
+    field = cells;
+  }
+
+  let data: String = digits.iter().map(|digit| DIGITS[*digit as usize] as char).collect();
+  let (first, rest) = data.split_at(data.len().min(FIRST_CHUNK));
+  let mut fumen = format!("{PREFIX}{first}");
+  for chunk in rest.as_bytes().chunks(CHUNK) {
+    fumen.push('?');
+    fumen.push_str(std::str::from_utf8(chunk).expect("failed to split fumen"));
+  }
+  fumen
+}
+
+// Actions //
+
+/// The piece on a page and what happens to it
+struct Flags {
+  piece: Option<PieceLocation>,
+  /// the garbage row rises into the field after the piece locks
+  rise: bool,
+  /// the field is mirrored after the piece locks
+  mirror: bool,
+  /// pieces are drawn in guideline colors
+  colorize: bool,
+  /// the comment changed on this page
+  comment: bool,
+  lock: bool,
+}
+
+impl Flags {
+  /// Read the flags written as `value`
+  fn decode(value: u32) -> Self {
+    let mut value = value;
+    let mut take = |base: u32| {
+      let digit = value % base;
+      value /= base;
+      digit
+    };
+
+    let code = take(8) as usize;
+    let orientation = ORIENTATIONS[take(4) as usize];
+    let position = take(FIELD_CELLS as u32) as i32;
+    let piece = code.checked_sub(1).and_then(|index| PIECES.get(index)).map(|shape_type| {
+      let offset = center_offset(*shape_type, orientation);
+      PieceLocation {
+        piece: String::from(shape_type.name()),
+        orientation,
+        x: position % FIELD_WIDTH as i32 + offset.x,
+        y: FIELD_TOP as i32 - position / FIELD_WIDTH as i32 - 1 + offset.y,
+      }
+    });
+
+    Self {
+      piece,
+      rise: take(2) == 1,
+      mirror: take(2) == 1,
+      colorize: take(2) == 1,
+      comment: take(2) == 1,
+      lock: take(2) == 0,
+    }
+  }
+
+  /// Get the value the flags are written as
+  fn encode(&self) -> u32 {
+    let flag = |set: bool| set as u32;
+    let (code, orientation, position) = match &self.piece {
+      Some(location) => {
+        let (shape_type, index) = location_index(location).expect("failed to place piece on the field");
+        (piece_code(shape_type).expect("failed to find piece code"), location.orientation, index)
+      }
+      None => (EMPTY, Orientation::South, 0),
+    };
+    let rotation = ORIENTATIONS.iter().position(|other| *other == orientation).expect("failed to find orientation code");
+
+    let mut value = flag(!self.lock);
+    for set in [self.comment, self.colorize, self.mirror, self.rise] {
+      value = value * 2 + flag(set);
+    }
+    value = value * FIELD_CELLS as u32 + position;
+    value = value * 4 + rotation as u32;
+    value * 8 + code as u32
+  }
+}
+
+/// Get the code of `shape_type`, if it is one of the pieces fumen can write
+fn piece_code(shape_type: ShapeType) -> Option<u8> {
+  PIECES.iter().position(|piece| *piece == shape_type).map(|index| index as u8 + 1)
+}
+
+/// Get the piece at `location` and the index of the cell fumen writes it at, if it is on the field
+fn location_index(location: &PieceLocation) -> Option<(ShapeType, u32)> {
+  let shape_type = ShapeType::from_name(&location.piece)?;
+  let offset = center_offset(shape_type, location.orientation);
+  let (x, row) = (location.x - offset.x, FIELD_TOP as i32 - 1 - (location.y - offset.y));
+  let on_field = (0..FIELD_WIDTH as i32).contains(&x) && (0..FIELD_TOP as i32).contains(&row);
+  on_field.then_some((shape_type, (row * FIELD_WIDTH as i32 + x) as u32))
+}
+
+/// Get the offset from where fumen writes a piece to its rotation center
+///
+/// Fumen keeps the centers of an older rotation system where the O never moves, and the I, S, and Z are the
+/// same upside down.
+fn center_offset(shape_type: ShapeType, orientation: Orientation) -> Coordinate {
+  match (shape_type, orientation) {
+    (ShapeType::O, Orientation::West) => Coordinate::new(1, -1),
+    (ShapeType::O, Orientation::South) => Coordinate::new(1, 0),
+    (ShapeType::O, Orientation::North) => Coordinate::new(0, -1),
+    (ShapeType::I, Orientation::South) => Coordinate::new(1, 0),
+    (ShapeType::I, Orientation::West) => Coordinate::new(0, -1),
+    (ShapeType::S, Orientation::North) => Coordinate::new(0, -1),
+    (ShapeType::S, Orientation::East) => Coordinate::new(-1, 0),
+    (ShapeType::Z, Orientation::North) => Coordinate::new(0, -1),
+    (ShapeType::Z, Orientation::West) => Coordinate::new(1, 0),
+    _ => Coordinate::new(0, 0),
+  }
+}
+
+// Fields //
+
+/// Get the rows a fumen field extends above our matrix, negative if the matrix is taller
+fn row_offset() -> i32 {
+  FIELD_TOP as i32 - MATRIX_DIMENSIONS.y as i32
+}
+
+/// Get the tiles of our board from `cells`, dropping any that don't fit
+fn to_board(cells: &Cells) -> Vec<(Coordinate, TileId)> {
+  cells[..FIELD_TOP * FIELD_WIDTH].iter()
+    .enumerate()
+    .filter(|(_, code)| **code != EMPTY)
+    .map(|(index, code)| {
+      let coordinate = Coordinate::new((index % FIELD_WIDTH) as i32, (index / FIELD_WIDTH) as i32 - row_offset());
+      let tile_id = match PIECES.get(*code as usize - 1) {
+        Some(shape_type) => shape_type.data().tile_id,
+        None => GARBAGE_TILE_ID,
+      };
+      (coordinate, tile_id)
+    })
+    .filter(|(coordinate, _)| coordinate.x < MATRIX_DIMENSIONS.x as i32 && coordinate.y >= 0 && coordinate.y < MATRIX_DIMENSIONS.y as i32)
+    .collect()
+}
+
+/// Get the field of the tiles of our board, dropping any that don't fit
+fn from_board(tiles: &[(Coordinate, TileId)]) -> Cells {
+  let mut cells = [EMPTY; FIELD_CELLS];
+  for (coordinate, tile_id) in tiles {
+    let row = coordinate.y + row_offset();
+    if (0..FIELD_WIDTH as i32).contains(&coordinate.x) && (0..FIELD_TOP as i32).contains(&row) {
+      let code = PIECES.iter().find(|shape_type| shape_type.data().tile_id == *tile_id).and_then(|shape_type| piece_code(*shape_type)).unwrap_or(GARBAGE);
+      cells[row as usize * FIELD_WIDTH + coordinate.x as usize] = code;
+    }
+  }
+  cells
+}
+
+/// Fill the cells of a piece of `shape_type` at `pose` on `cells`
+fn lock(cells: &mut Cells, shape_type: ShapeType, pose: &Pose) {
+  let Some(code) = piece_code(shape_type) else {
+    return;
+  };
+  for mino in &shape_type.data().shape[pose.rotation] {
+    let (x, row) = (pose.position.x + mino.x, pose.position.y + mino.y);
+    if (0..FIELD_WIDTH as i32).contains(&x) && (0..FIELD_TOP as i32).contains(&row) {
+      cells[row as usize * FIELD_WIDTH + x as usize] = code;
+    }
+  }
+}
+
+/// Remove the full rows above the garbage row, moving the rows above them down
+fn clear_lines(cells: &mut Cells) {
+  let rows: Vec<&[u8]> = cells[..FIELD_TOP * FIELD_WIDTH]
+    .chunks(FIELD_WIDTH)
+    .filter(|row| row.contains(&EMPTY))
+    .collect();
+
+  let mut cleared = vec![EMPTY; (FIELD_TOP - rows.len()) * FIELD_WIDTH];
+  cleared.extend(rows.concat());
+  cells[..FIELD_TOP * FIELD_WIDTH].copy_from_slice(&cleared);
+}
+
+/// Push the field up a row, moving the garbage row into it
+fn rise(cells: &mut Cells) {
+  cells.copy_within(FIELD_WIDTH.., 0);
+  cells[FIELD_TOP * FIELD_WIDTH..].fill(EMPTY);
+}
+
+/// Flip the rows above the garbage row left to right
+fn mirror(cells: &mut Cells) {
+  for row in cells[..FIELD_TOP * FIELD_WIDTH].chunks_mut(FIELD_WIDTH) {
+    row.reverse();
+  }
+}
+
+/// Read the changes from the previous field onto `cells`
+///
+/// returns `false` if nothing changed
+fn read_field(reader: &mut Reader, cells: &mut Cells) -> Result<bool, String> {
+  let mut changed = true;
+  let mut index = 0;
+  while index < FIELD_CELLS {
+    let value = reader.read(2)?;
+    let (difference, count) = (value / FIELD_CELLS as u32, value as usize % FIELD_CELLS + 1);
+    if difference == NO_CHANGE && count == FIELD_CELLS {
+      changed = false;
+    }
+
+    let cells = cells.get_mut(index..index + count).ok_or("fumen field is too large")?;
+    for cell in cells {
+      let code = (*cell as u32 + difference).checked_sub(NO_CHANGE).filter(|code| *code <= GARBAGE as u32);
+      *cell = code.ok_or("invalid fumen cell")? as u8;
+    }
+    index += count;
+  }
+  Ok(changed)
+}
+
+/// Write the changes from `previous` to `cells` as runs of the same difference
+fn write_field(digits: &mut Vec<u32>, previous: &Cells, cells: &Cells) {
+  let difference = |index: usize| cells[index] as u32 + NO_CHANGE - previous[index] as u32;
+  let mut start = 0;
+  for index in 1..=FIELD_CELLS {
+    if index == FIELD_CELLS || difference(index) != difference(start) {
+      write(digits, difference(start) * FIELD_CELLS as u32 + (index - start - 1) as u32, 2);
+      start = index;
+    }
+  }
+}
+
+// Comments //
+
+/// Read a comment, its length then groups of characters
+fn read_comment(reader: &mut Reader) -> Result<String, String> {
+  let length = reader.read(2)? as usize;
+  let mut escaped = String::new();
+  for _ in 0..length.div_ceil(COMMENT_GROUP) {
+    let mut value = reader.read(5)?;
+    for _ in 0..COMMENT_GROUP {
+      escaped.push(char::from_u32(value % COMMENT_BASE + COMMENT_FIRST).ok_or("invalid fumen comment")?);
+      value /= COMMENT_BASE;
+    }
+  }
+  escaped.truncate(length);
+  Ok(unescape(&escaped))
+}
+
+/// Write `comment`, its length then groups of characters
+fn write_comment(digits: &mut Vec<u32>, comment: &str) {
+  let mut escaped = escape(comment);
+  escaped.truncate(MAX_COMMENT);
+  write(digits, escaped.len() as u32, 2);
+  for group in escaped.as_bytes().chunks(COMMENT_GROUP) {
+    let value = group.iter().rev().fold(0, |value, character| value * COMMENT_BASE + (*character as u32 - COMMENT_FIRST));
+    write(digits, value, 5);
+  }
+}
+
+/// Escape `text` the way JavaScript's `escape` does
+fn escape(text: &str) -> String {
+  let mut escaped = String::new();
+  for unit in text.encode_utf16() {
+    match char::from_u32(unit as u32) {
+      Some(character) if character.is_ascii_alphanumeric() || UNESCAPED.contains(character) => escaped.push(character),
+      _ if unit < 0x100 => escaped.push_str(&format!("%{unit:02X}")),
+      _ => escaped.push_str(&format!("%u{unit:04X}")),
+    }
+  }
+  escaped
+}
+
+/// Reverse the escapes of `text`, leaving anything that isn't an escape as it is
+fn unescape(text: &str) -> String {
+  let hex = |digits: Option<&str>| digits
+    .filter(|digits| digits.chars().all(|digit| digit.is_ascii_hexdigit()))
+    .and_then(|digits| u16::from_str_radix(digits, 16).ok());
+
+  let mut units = Vec::new();
+  let mut rest = text;
+  while let Some(character) = rest.chars().next() {
+    let escaped = match character {
+      '%' if rest.starts_with("%u") => hex(rest.get(2..6)).map(|unit| (unit, 6)),
+      '%' => hex(rest.get(1..3)).map(|unit| (unit, 3)),
+      _ => None,
+    };
+    match escaped {
+      Some((unit, length)) => {
+        units.push(unit);
+        rest = &rest[length..];
+      }
+      None => {
+        units.extend_from_slice(character.encode_utf16(&mut [0; 2]));
+        rest = &rest[character.len_utf8()..];
+      }
+    }
+  }
+  String::from_utf16_lossy(&units)
+}
+
+// Digits //
+
+/// Reads base 64 numbers from the data
+struct Reader {
+  digits: Vec<u32>,
+  index: usize,
+}
+
+impl Reader {
+  /// Instantiate a reader of `data`, ignoring separators and whitespace
+  fn new(data: &str) -> Result<Self, String> {
+    let digits = data.chars()
+      .filter(|character| *character != '?' && !character.is_whitespace())
+      .map(|character| DIGITS.iter()
+        .position(|digit| *digit as char == character)
+        .map(|digit| digit as u32)
+        .ok_or(format!("invalid fumen character {character}")))
+      .collect::<Result<_, _>>()?;
+    Ok(Self { digits, index: 0 })
+  }
+
+  /// Check if every digit has been read
+  fn is_empty(&self) -> bool {
+    self.index >= self.digits.len()
+  }
+
+  /// Read a number written in `count` digits
+  fn read(&mut self, count: usize) -> Result<u32, String> {
+    let digits = self.digits.get(self.index..self.index + count).ok_or("fumen data ended early")?;
+    self.index += count;
+    Ok(digits.iter().rev().fold(0, |value, digit| value * 64 + digit))
+  }
+}
+
+/// Write `value` in `count` digits
+fn write(digits: &mut Vec<u32>, value: u32, count: usize) {
+  let mut value = value;
+  for _ in 0..count {
+    digits.push(value % 64);
+    value /= 64;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use super::*;
+
+  /// Write the digits of a page with an unchanged empty field, then `value` as its action
+  fn fumen_of_action(value: u32) -> String {
+    let mut digits = Vec::new();
+    write(&mut digits, NO_CHANGE * FIELD_CELLS as u32 + FIELD_CELLS as u32 - 1, 2);
+    write(&mut digits, 0, 1);
+    write(&mut digits, value, 3);
+    let data: String = digits.iter().map(|digit| DIGITS[*digit as usize] as char).collect();
+    format!("{PREFIX}{data}")
+  }
+
+  /// Get the cells of our board filled by `piece`
+  fn piece_cells((shape_type, pose): (ShapeType, Pose)) -> HashSet<Coordinate> {
+    shape_type.data().shape[pose.rotation].iter().map(|mino| *mino + pose.position).collect()
+  }
+
+  #[test]
+  fn decodes_an_empty_page() {
+    let pages = decode("v115@vhAAgH").unwrap();
+    assert_eq!(pages, vec![Page { lock: true, ..Page::default() }]);
+  }
+
+  #[test]
+  fn decodes_from_a_link() {
+    let pages = decode("https://harddrop.com/fumen/?v115@vhAAgH").unwrap();
+    assert_eq!(pages.len(), 1);
+  }
+
+  #[test]
+  fn decodes_a_field() {
+    // four rows of six garbage on the floor
+    let pages = decode("v115@9gF8DeF8DeF8DeF8NeAgH").unwrap();
+    let bottom = MATRIX_DIMENSIONS.y as i32 - 1;
+    let expected: HashSet<(Coordinate, TileId)> = (bottom - 3..=bottom)
+      .flat_map(|y| (0..6).map(move |x| (Coordinate::new(x, y), GARBAGE_TILE_ID)))
+      .collect();
+    assert_eq!(pages[0].cells.iter().copied().collect::<HashSet<_>>(), expected);
+  }
+
+  #[test]
+  fn decodes_a_comment() {
+    let pages = decode("v115@vhAAgWEA0YceE").unwrap();
+    assert_eq!(pages[0].comment, "test");
+  }
+
+  #[test]
+  fn decodes_repeated_pages() {
+    let pages = decode("v115@vhCAgHAAAAAA").unwrap();
+    assert_eq!(pages.len(), 3);
+    assert!(pages.iter().all(|page| page.cells.is_empty() && page.piece.is_none()));
+  }
+
+  #[test]
+  fn rejects_invalid_data() {
+    assert!(decode("vhAAgH").is_err());
+    assert!(decode("v115@vhAA").is_err());
+    assert!(decode("v115@vh!AgH").is_err());
+  }
+
+  /// Decode a piece of `shape_type` written at `index` in the orientation of `code`, returning the cells it fills
+  fn decode_piece(shape_type: ShapeType, index: i32, code: usize) -> HashSet<Coordinate> {
+    let piece = piece_code(shape_type).unwrap() as u32;
+    let value = ((4 * FIELD_CELLS as u32 + index as u32) * 4 + code as u32) * 8 + piece;
+    let pages = decode(&fumen_of_action(value)).unwrap();
+    let decoded = pages[0].piece.expect("failed to decode the piece");
+    assert_eq!(decoded.0, shape_type);
+    piece_cells(decoded)
+  }
+
+  #[test]
+  fn decodes_every_piece_and_rotation_where_fumen_draws_it() {
+    // the minos of each piece in the spawn orientation around where fumen writes it, y up, and the orientation codes
+    // it's drawn turned about that point in
+    let spawn = [
+      (ShapeType::I, [(0, 0), (-1, 0), (1, 0), (2, 0)], vec![1, 2]),
+      (ShapeType::L, [(0, 0), (-1, 0), (1, 0), (1, 1)], vec![0, 1, 2, 3]),
+      (ShapeType::O, [(0, 0), (1, 0), (0, 1), (1, 1)], vec![1]),
+      (ShapeType::Z, [(0, 0), (1, 0), (0, 1), (-1, 1)], vec![0, 1]),
+      (ShapeType::T, [(0, 0), (-1, 0), (1, 0), (0, 1)], vec![0, 1, 2, 3]),
+      (ShapeType::J, [(0, 0), (-1, 0), (1, 0), (-1, 1)], vec![0, 1, 2, 3]),
+      (ShapeType::S, [(0, 0), (-1, 0), (0, 1), (1, 1)], vec![0, 3]),
+    ];
+    // clockwise turns from spawn of each orientation code
+    let turns = [2, 1, 0, 3];
+    let (x, y) = (4, 10);
+    let index = (FIELD_TOP as i32 - 1 - y) * FIELD_WIDTH as i32 + x;
+
+    for (shape_type, minos, turned) in spawn {
+      let name = shape_type.name();
+      for code in turned.iter().copied() {
+        let expected: HashSet<Coordinate> = minos.iter()
+          .map(|&mino| (0..turns[code]).fold(mino, |(x, y), _| (y, -x)))
+          .map(|(dx, dy)| Coordinate::new(x + dx, FIELD_TOP as i32 - 1 - (y + dy) - row_offset()))
+          .collect();
+        assert_eq!(decode_piece(shape_type, index, code), expected, "{name} in orientation {code}");
+      }
+
+      // fumen draws the I, S, and Z the same upside down, and the O the same every way around
+      for code in 0..ORIENTATIONS.len() {
+        let same = match shape_type {
+          ShapeType::O => 1,
+          ShapeType::I | ShapeType::S | ShapeType::Z if !turned.contains(&code) => (code + 2) % 4,
+          _ => continue,
+        };
+        assert_eq!(decode_piece(shape_type, index, code), decode_piece(shape_type, index, same), "{name} in orientation {code}");
+      }
+    }
+  }
+
+  #[test]
+  fn round_trips_every_piece_and_rotation() {
+    let bottom = MATRIX_DIMENSIONS.y as i32 - 6;
+    let pages: Vec<Page> = ShapeType::STANDARD.iter()
+      .flat_map(|shape_type| (0..shape_type.data().shape.len()).map(move |rotation| (*shape_type, rotation)))
+      .map(|(shape_type, rotation)| Page {
+        piece: Some((shape_type, Pose::new(rotation, Coordinate::new(3, bottom)))),
+        ..Page::default()
+      })
+      .collect();
+    assert_eq!(decode(&encode(&pages)).unwrap(), pages);
+  }
+
+  #[test]
+  fn round_trips_locked_pieces_and_fields() {
+    let bottom = MATRIX_DIMENSIONS.y as i32 - 1;
+    let garbage: Vec<(Coordinate, TileId)> = (1..10).map(|x| (Coordinate::new(x, bottom), GARBAGE_TILE_ID)).collect();
+    let first = Page {
+      cells: garbage,
+      piece: Some((ShapeType::I, Pose::new(1, Coordinate::new(-2, bottom - 3)))),
+      comment: String::from("fill the well"),
+      lock: true,
+    };
+
+    // the I filled the well and cleared the bottom line, leaving the rest of the I
+    let second = Page {
+      cells: (bottom - 2..=bottom).map(|y| (Coordinate::new(0, y), ShapeType::I.data().tile_id)).collect(),
+      comment: first.comment.clone(),
+      ..Page::default()
+    };
+
+    let fumen = encode(&[first.clone(), second.clone()]);
+    assert_eq!(decode(&fumen).unwrap(), vec![first, second]);
+    // the field after locking is written as unchanged, and the comment isn't written again
+    assert!(fumen.replace('?', "").ends_with("vhAAAe"));
+  }
+
+  #[test]
+  fn round_trips_repeated_pages() {
+    let page = Page {
+      cells: vec![(Coordinate::new(0, MATRIX_DIMENSIONS.y as i32 - 1), GARBAGE_TILE_ID)],
+      ..Page::default()
+    };
+    for count in [1, 2, 64, 65, 130] {
+      let pages = vec![page.clone(); count];
+      assert_eq!(decode(&encode(&pages)).unwrap(), pages, "{count} repeated pages");
+    }
+  }
+
+  #[test]
+  fn round_trips_escaped_comments() {
+    for comment in ["50% off!", "caf\u{e9}", "\u{3042}\u{3044}", "@*_+-./", ""] {
+      let page = Page { comment: String::from(comment), ..Page::default() };
+      assert_eq!(decode(&encode(std::slice::from_ref(&page))).unwrap(), vec![page], "{comment}");
+    }
+    assert_eq!(escape("50% off!"), "50%25%20off%21");
+    assert_eq!(escape("\u{3042}"), "%u3042");
+  }
+
+  #[test]
+  fn splits_long_fumens() {
+    let pages: Vec<Page> = (0..20).map(|index| Page { comment: index.to_string(), ..Page::default() }).collect();
+    let fumen = encode(&pages);
+    let chunks: Vec<&str> = fumen[PREFIX.len()..].split('?').collect();
+    assert_eq!(chunks[0].len(), FIRST_CHUNK);
+    assert!(chunks[1..].iter().all(|chunk| chunk.len() <= CHUNK));
+    assert_eq!(decode(&fumen).unwrap(), pages);
+  }
+}
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+
+/**
+ * Queue garbage sent by an opponent
+ */
+
+/// Garbage waiting to be added to a board, in the order it was received
+#[derive(Default, Debug)]
+pub struct GarbageQueue {
+  pending: VecDeque<u32>,
+}
+
+impl GarbageQueue {
+  /// Instantiate a new empty queue
+  pub fn new() -> Self {
+    Self { pending: VecDeque::new() }
+  }
+
+  /// Queue `lines` of garbage
+  pub fn push(&mut self, lines: u32) {
+    if lines > 0 {
+      self.pending.push_back(lines);
+    }
+  }
+
+  /// Cancel queued garbage with `attack`, oldest first
+  ///
+  /// returns the attack remaining after all garbage is cancelled
+  pub fn cancel(&mut self, mut attack: u32) -> u32 {
+    while attack > 0 {
+      let Some(lines) = self.pending.front_mut() else { break; };
+      let cancelled = attack.min(*lines);
+      *lines -= cancelled;
+      attack -= cancelled;
+      if *lines == 0 {
+        self.pending.pop_front();
+      }
+    }
+    attack
+  }
+
+  /// Remove up to `max` lines of garbage from the queue, returning each chunk
+  pub fn take(&mut self, max: u32) -> Vec<u32> {
+    let mut chunks = Vec::new();
+    let mut remaining = max;
+    while remaining > 0 {
+      let Some(lines) = self.pending.front_mut() else { break; };
+      let taken = remaining.min(*lines);
+      *lines -= taken;
+      remaining -= taken;
+      chunks.push(taken);
+      if *lines == 0 {
+        self.pending.pop_front();
+      }
+    }
+    chunks
+  }
+
+  /// Total lines of garbage queued
+  pub fn total(&self) -> u32 {
+    self.pending.iter().sum()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ignores_empty_attacks() {
+    let mut queue = GarbageQueue::new();
+    queue.push(0);
+    assert_eq!(queue.total(), 0);
+    assert!(queue.take(4).is_empty());
+  }
+
+  #[test]
+  fn cancels_oldest_garbage_first() {
+    let mut queue = GarbageQueue::new();
+    queue.push(2);
+    queue.push(4);
+
+    assert_eq!(queue.cancel(3), 0);
+    assert_eq!(queue.total(), 3);
+    assert_eq!(queue.take(8), vec![3]);
+  }
+
+  #[test]
+  fn returns_attack_left_after_cancelling_everything() {
+    let mut queue = GarbageQueue::new();
+    queue.push(1);
+    queue.push(2);
+
+    assert_eq!(queue.cancel(5), 2);
+    assert_eq!(queue.total(), 0);
+  }
+
+  #[test]
+  fn takes_chunks_in_order_up_to_max() {
+    let mut queue = GarbageQueue::new();
+    queue.push(1);
+    queue.push(3);
+    queue.push(2);
+
+    assert_eq!(queue.take(5), vec![1, 3, 1]);
+    assert_eq!(queue.total(), 1);
+    assert_eq!(queue.take(5), vec![1]);
+    assert_eq!(queue.total(), 0);
+  }
+}
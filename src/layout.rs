@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::constants::game::{BOARD_DIMENSIONS, BOARD_POSITION, BORDER_COLOR, FAULT_COLOR, FAULT_TEXT_POSITION, FINESSE_TEXT_POSITION, GARBAGE_METER_COLOR, GARBAGE_METER_POSITION, GARBAGE_METER_WIDTH, LEVEL_TEXT_POSITION, LINES_TEXT_POSITION, NEXT_TEXT_POSITION, OBJECTIVE_TEXT_POSITION, PREVIEW_BORDER, PREVIEW_POSITION, SCORE_TEXT_POSITION, STANDARD_LAYOUT, STATE_TEXT_POSITION, STATISTICS_BORDER, TILE_SIZE, TOP_OUT_TEXT_POSITION, TYPEFACE};
+use crate::constants::window::SCREEN_PIXELS;
+use crate::engine::geometry::{Rec2, Vec2};
+use crate::engine::render::color::{color, RGBA};
+use crate::engine::storage::read_json;
+use crate::engine::utility::types::{Coordinate, Size, Size2};
+
+/**
+ * Where the board and its HUD are drawn, the standard layout or one read from a file
+ */
+
+static LAYOUT: OnceLock<Layout> = OnceLock::new();
+
+/// The corner of the board a part of the HUD keeps its distance from as the board is resized
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+  /// stays put
+  #[default]
+  TopLeft,
+  /// moves right as the board widens
+  TopRight,
+  /// moves down as the board heightens
+  BottomLeft,
+  /// moves right and down as the board grows
+  BottomRight,
+}
+
+impl Anchor {
+  /// Get how far a part anchored here moves from beside a standard board to beside a board of `dimensions`
+  pub fn shift(self, dimensions: Size2) -> Coordinate {
+    let x = (dimensions.x as i32 - BOARD_DIMENSIONS.x as i32) * TILE_SIZE as i32;
+    let y = (dimensions.y as i32 - BOARD_DIMENSIONS.y as i32) * TILE_SIZE as i32;
+    match self {
+      Anchor::TopLeft => Vec2::new(0, 0),
+      Anchor::TopRight => Vec2::new(x, 0),
+      Anchor::BottomLeft => Vec2::new(0, y),
+      Anchor::BottomRight => Vec2::new(x, y),
+    }
+  }
+}
+
+/// Where a part of the HUD is drawn beside a standard board
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Placement {
+  pub position: Coordinate,
+  #[serde(default)]
+  pub anchor: Anchor,
+}
+
+impl Placement {
+  /// Get where the part is drawn beside a board of `dimensions`
+  pub fn position(&self, dimensions: Size2) -> Coordinate {
+    self.position + self.anchor.shift(dimensions)
+  }
+}
+
+/// A rectangle outlined around part of the HUD
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Panel {
+  #[serde(flatten)]
+  pub placement: Placement,
+  pub size: Size2,
+  #[serde(default = "border_color")]
+  pub color: RGBA,
+}
+
+impl Panel {
+  /// Get the rectangle outlined beside a board of `dimensions`
+  pub fn rect(&self, dimensions: Size2) -> Rec2<i32, Size> {
+    Rec2::new(self.placement.position(dimensions), self.size)
+  }
+}
+
+/// What a text of the HUD shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Readout {
+  Score,
+  Lines,
+  Level,
+  /// the title of the preview
+  Next,
+  /// paused, won, or game over
+  State,
+  /// why the game ended
+  TopOut,
+  /// the objective of a puzzle
+  Objective,
+  /// the finesse faults of a player at the keyboard
+  Finesse,
+  /// a warning after a finesse fault
+  Fault,
+}
+
+impl Readout {
+  /// Get the color the readout is drawn in unless the layout chooses another
+  pub fn color(self) -> RGBA {
+    match self {
+      Readout::Fault => FAULT_COLOR,
+      _ => color::TEXT,
+    }
+  }
+}
+
+/// A text of the HUD
+#[derive(Clone, Debug, Deserialize)]
+pub struct Label {
+  pub readout: Readout,
+  #[serde(flatten)]
+  pub placement: Placement,
+  pub color: Option<RGBA>,
+  /// name of the typeface drawn in
+  #[serde(default = "default_font")]
+  pub font: String,
+}
+
+/// A typeface to load for labels to be drawn in
+#[derive(Clone, Debug, Deserialize)]
+pub struct Font {
+  pub name: String,
+  pub path: String,
+  pub size: u16,
+}
+
+/// The bar showing the garbage waiting to be added, rising from the bottom of the board
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct GarbageMeter {
+  /// where the top of the bar is drawn when full
+  #[serde(flatten)]
+  pub placement: Placement,
+  pub width: Size,
+  #[serde(default = "garbage_meter_color")]
+  pub color: RGBA,
+}
+
+/// Where the board and each part of its HUD are drawn
+#[derive(Clone, Debug, Deserialize)]
+pub struct Layout {
+  pub name: String,
+  /// pixels taken up by a standard board and its HUD
+  pub size: Size2,
+  pub board: Coordinate,
+  /// where the top left of the preview of the next piece is drawn
+  pub preview: Placement,
+  pub garbage_meter: GarbageMeter,
+  #[serde(default)]
+  pub panels: Vec<Panel>,
+  /// the texts of the HUD, readouts left out aren't shown
+  #[serde(default)]
+  pub labels: Vec<Label>,
+  /// typefaces to load beside the standard typeface
+  #[serde(default)]
+  pub fonts: Vec<Font>,
+}
+
+impl Layout {
+  /// Build the standard layout, with the HUD to the right of the board
+  pub fn standard() -> Self {
+    let right = |position| Placement { position, anchor: Anchor::TopRight };
+    let panel = |rect: Rec2<i32, Size>| Panel { placement: right(rect.origin), size: rect.size, color: BORDER_COLOR };
+    let label = |readout, position| Label { readout, placement: right(position), color: None, font: default_font() };
+
+    Self {
+      name: String::from(STANDARD_LAYOUT),
+      size: SCREEN_PIXELS,
+      board: BOARD_POSITION,
+      preview: right(PREVIEW_POSITION),
+      garbage_meter: GarbageMeter {
+        placement: right(GARBAGE_METER_POSITION),
+        width: GARBAGE_METER_WIDTH,
+        color: GARBAGE_METER_COLOR,
+      },
+      panels: vec![panel(STATISTICS_BORDER), panel(PREVIEW_BORDER)],
+      labels: vec![
+        label(Readout::Score, SCORE_TEXT_POSITION),
+        label(Readout::Lines, LINES_TEXT_POSITION),
+        label(Readout::Level, LEVEL_TEXT_POSITION),
+        label(Readout::Next, NEXT_TEXT_POSITION),
+        label(Readout::State, STATE_TEXT_POSITION),
+        label(Readout::TopOut, TOP_OUT_TEXT_POSITION),
+        label(Readout::Objective, OBJECTIVE_TEXT_POSITION),
+        label(Readout::Finesse, FINESSE_TEXT_POSITION),
+        label(Readout::Fault, FAULT_TEXT_POSITION),
+      ],
+      fonts: Vec::new(),
+    }
+  }
+
+  /// Read a layout from `path`
+  pub fn load(path: &Path) -> Result<Self, String> {
+    let layout: Self = read_json(path)?;
+    layout.validate()?;
+    Ok(layout)
+  }
+
+  /// Check each readout is placed once, in a font that is loaded from a file that exists
+  fn validate(&self) -> Result<(), String> {
+    let mut readouts = HashSet::new();
+    if let Some(label) = self.labels.iter().find(|label| !readouts.insert(label.readout)) {
+      return Err(format!("{:?} is placed twice in layout {}", label.readout, self.name));
+    }
+    let known_font = |name: &str| name == TYPEFACE || self.fonts.iter().any(|font| font.name == name);
+    if let Some(label) = self.labels.iter().find(|label| !known_font(&label.font)) {
+      return Err(format!("{:?} is drawn in unknown font {} in layout {}", label.readout, label.font, self.name));
+    }
+    if let Some(font) = self.fonts.iter().find(|font| !Path::new(&font.path).is_file()) {
+      return Err(format!("font {} has no file at {} in layout {}", font.name, font.path, self.name));
+    }
+    Ok(())
+  }
+
+  /// Get the label showing `readout`, if it's shown
+  pub fn label(&self, readout: Readout) -> Option<&Label> {
+    self.labels.iter().find(|label| label.readout == readout)
+  }
+}
+
+fn default_font() -> String {
+  String::from(TYPEFACE)
+}
+
+fn border_color() -> RGBA {
+  BORDER_COLOR
+}
+
+fn garbage_meter_color() -> RGBA {
+  GARBAGE_METER_COLOR
+}
+
+/// Draw the board and HUD with `layout` instead of the standard layout
+///
+/// The layout can only be chosen before any players are built.
+pub fn use_layout(layout: Layout) -> Result<(), String> {
+  LAYOUT.set(layout).map_err(|layout| format!("failed to use layout {}, a layout is already in use", layout.name))
+}
+
+/// Get the layout in use
+pub fn layout() -> &'static Layout {
+  LAYOUT.get_or_init(Layout::standard)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Build a font named `name` loaded from `path`
+  fn font(name: &str, path: &str) -> Font {
+    Font { name: String::from(name), path: String::from(path), size: 8 }
+  }
+
+  #[test]
+  fn places_the_standard_layout_where_the_constants_do() {
+    let layout = Layout::standard();
+    let position = |readout| layout.label(readout).unwrap().placement.position(BOARD_DIMENSIONS);
+
+    assert_eq!(layout.board, BOARD_POSITION);
+    assert_eq!(layout.preview.position(BOARD_DIMENSIONS), PREVIEW_POSITION);
+    assert_eq!(layout.garbage_meter.placement.position(BOARD_DIMENSIONS), GARBAGE_METER_POSITION);
+    assert_eq!(layout.panels.len(), 2);
+    for (panel, border) in layout.panels.iter().zip([STATISTICS_BORDER, PREVIEW_BORDER]) {
+      let rect = panel.rect(BOARD_DIMENSIONS);
+      assert_eq!((rect.origin, rect.size), (border.origin, border.size));
+    }
+    assert_eq!(position(Readout::Score), SCORE_TEXT_POSITION);
+    assert_eq!(position(Readout::Lines), LINES_TEXT_POSITION);
+    assert_eq!(position(Readout::Level), LEVEL_TEXT_POSITION);
+    assert_eq!(position(Readout::Next), NEXT_TEXT_POSITION);
+    assert_eq!(position(Readout::State), STATE_TEXT_POSITION);
+    assert_eq!(position(Readout::TopOut), TOP_OUT_TEXT_POSITION);
+    assert_eq!(position(Readout::Objective), OBJECTIVE_TEXT_POSITION);
+    assert_eq!(position(Readout::Finesse), FINESSE_TEXT_POSITION);
+    assert_eq!(position(Readout::Fault), FAULT_TEXT_POSITION);
+  }
+
+  #[test]
+  fn shifts_parts_with_the_corner_they_are_anchored_to() {
+    let dimensions = BOARD_DIMENSIONS + Vec2::new(2, 3);
+    let (x, y) = (2 * TILE_SIZE as i32, 3 * TILE_SIZE as i32);
+    assert_eq!(Anchor::TopLeft.shift(dimensions), Vec2::new(0, 0));
+    assert_eq!(Anchor::TopRight.shift(dimensions), Vec2::new(x, 0));
+    assert_eq!(Anchor::BottomLeft.shift(dimensions), Vec2::new(0, y));
+    assert_eq!(Anchor::BottomRight.shift(dimensions), Vec2::new(x, y));
+
+    // a narrower board pulls the parts in
+    assert_eq!(Anchor::TopRight.shift(Vec2::new(BOARD_DIMENSIONS.x - 1, BOARD_DIMENSIONS.y)), Vec2::new(-(TILE_SIZE as i32), 0));
+  }
+
+  #[test]
+  fn loads_the_bundled_layouts() {
+    assert!(Layout::load(Path::new("asset/layouts/left.json")).is_ok());
+    assert!(Layout::standard().validate().is_ok());
+  }
+
+  #[test]
+  fn rejects_a_readout_placed_twice() {
+    let mut layout = Layout::standard();
+    layout.labels.push(layout.labels[0].clone());
+    assert_eq!(layout.validate().unwrap_err(), format!("Score is placed twice in layout {STANDARD_LAYOUT}"));
+  }
+
+  #[test]
+  fn rejects_a_label_in_an_unknown_font() {
+    let mut layout = Layout::standard();
+    layout.labels[0].font = String::from("big");
+    assert_eq!(layout.validate().unwrap_err(), format!("Score is drawn in unknown font big in layout {STANDARD_LAYOUT}"));
+
+    layout.fonts.push(font("big", "asset/typeface.ttf"));
+    assert!(layout.validate().is_ok());
+  }
+
+  #[test]
+  fn rejects_a_font_with_no_file() {
+    let mut layout = Layout::standard();
+    layout.fonts.push(font("big", "asset/missing.ttf"));
+    assert_eq!(layout.validate().unwrap_err(), format!("font big has no file at asset/missing.ttf in layout {STANDARD_LAYOUT}"));
+  }
+}
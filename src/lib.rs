@@ -0,0 +1,27 @@
+/**
+ * Game rules, AI, and engine shared by the game and its tools
+ */
+
+pub mod engine;
+pub mod constants;
+pub mod ai;
+pub mod piece;
+pub mod piece_set;
+pub mod board;
+pub mod algorithm;
+pub mod bot;
+pub mod controls;
+pub mod editor;
+pub mod finesse;
+pub mod fumen;
+pub mod garbage;
+pub mod layout;
+pub mod line_clear;
+pub mod menu;
+pub mod player;
+pub mod puzzle;
+pub mod save;
+pub mod settings;
+pub mod setup;
+pub mod simulation;
+pub mod tbp;
@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::constants::game::{DISSOLVE_DURATION, FLASH_COLOR, FLASH_DURATION, FLASH_INTERVAL, LINE_CLEAR_PARTICLES, MAX_LINES, WIPE_DURATION};
+use crate::engine::render::particle::ParticleSystem;
+use crate::engine::render::Renderer;
+use crate::engine::time::Timer;
+use crate::engine::utility::random::shuffle;
+use crate::engine::utility::types::{Coordinate, Size};
+
+/**
+ * Animations that remove full lines from the board before the lines above drop
+ */
+
+/// How the tiles of full lines are removed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearEffect {
+  /// the lines blink then vanish at once
+  Flash,
+  /// tiles vanish from the center of each line outward
+  Wipe,
+  /// tiles vanish one at a time in a random order
+  Dissolve,
+}
+
+impl ClearEffect {
+  /// Choose the effect for clearing `lines` at once, Tetrises flash and T-spins dissolve
+  pub fn choose(lines: u32, t_spin: bool) -> Self {
+    if lines >= MAX_LINES {
+      ClearEffect::Flash
+    } else if t_spin {
+      ClearEffect::Dissolve
+    } else {
+      ClearEffect::Wipe
+    }
+  }
+
+  /// Get how long the effect plays for
+  pub fn duration(&self) -> Duration {
+    match self {
+      ClearEffect::Flash => FLASH_DURATION,
+      ClearEffect::Wipe => WIPE_DURATION,
+      ClearEffect::Dissolve => DISSOLVE_DURATION,
+    }
+  }
+}
+
+/// Lines being cleared when a game was saved
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClearSave {
+  effect: ClearEffect,
+  lines: Vec<usize>,
+  remaining: Duration,
+}
+
+/// Removes the tiles of full lines over the duration of an effect
+#[derive(Debug)]
+pub struct ClearAnimation {
+  effect: ClearEffect,
+  lines: Vec<usize>,
+  timer: Timer,
+  /// each cell of the lines and how far through the animation it is removed, in the order they are removed
+  cells: Vec<(Coordinate, f32)>,
+  /// cells removed so far
+  removed: usize,
+}
+
+impl ClearAnimation {
+  /// Begin clearing `lines` of a board `width` wide with `effect`
+  pub fn new(effect: ClearEffect, lines: Vec<usize>, width: Size) -> Self {
+    let timer = Timer::new(effect.duration(), true);
+    let cells = removal_order(effect, &lines, width);
+    Self { effect, lines, timer, cells, removed: 0 }
+  }
+
+  /// Save the lines and the time left to resume later
+  pub fn save(&self) -> ClearSave {
+    ClearSave {
+      effect: self.effect,
+      lines: self.lines.clone(),
+      remaining: self.remaining(),
+    }
+  }
+
+  /// Continue clearing the lines in `save` on a board `width` wide
+  pub fn load(save: &ClearSave, width: Size) -> Self {
+    let mut animation = Self::new(save.effect, save.lines.clone(), width);
+    animation.timer.resume(Some(save.remaining));
+    animation
+  }
+
+  /// Get the lines being cleared
+  pub fn lines(&self) -> &[usize] {
+    &self.lines
+  }
+
+  /// Get the time left until the lines are clear
+  pub fn remaining(&self) -> Duration {
+    self.timer.remaining().unwrap_or_default()
+  }
+
+  /// Get how far through the animation is, from 0 to 1
+  fn progress(&self) -> f32 {
+    let duration = self.effect.duration().as_secs_f32();
+    1.0 - self.remaining().as_secs_f32() / duration
+  }
+
+  /// Remove the tiles due to be removed from `board`, bursting each visible tile into `particles`
+  ///
+  /// returns `true` once every tile of the lines is removed
+  pub fn update(&mut self, board: &mut Board, particles: &mut ParticleSystem) -> bool {
+    let done = self.timer.done();
+    let progress = if done { 1.0 } else { self.progress() };
+    let first_row = board.first_row();
+    while let Some((cell, due)) = self.cells.get(self.removed) {
+      if *due > progress {
+        break;
+      }
+      if let Some(tile_id) = board.clear_tile(cell).filter(|_| cell.y >= first_row) {
+        particles.emit(&LINE_CLEAR_PARTICLES, board.cell_area(cell), tile_id);
+      }
+      self.removed += 1;
+    }
+    done
+  }
+
+  /// Draw the effect over the lines of `board`
+  pub fn render(&self, board: &Board, renderer: &mut Renderer) {
+    if self.effect != ClearEffect::Flash {
+      return; // the other effects only remove tiles
+    }
+
+    let elapsed = self.effect.duration().saturating_sub(self.remaining());
+    let lit = (elapsed.as_millis() / FLASH_INTERVAL.as_millis()) % 2 == 0;
+    if lit {
+      let row: Vec<Coordinate> = (0..board.matrix_dimensions().x as i32).map(|x| Coordinate::new(x, 0)).collect();
+      for line in &self.lines {
+        board.render_outline(renderer, &row, &Coordinate::new(0, *line as i32), FLASH_COLOR);
+      }
+    }
+  }
+}
+
+/// Get each cell of `lines` on a board `width` wide, and how far through `effect` it is removed, soonest first
+fn removal_order(effect: ClearEffect, lines: &[usize], width: Size) -> Vec<(Coordinate, f32)> {
+  let mut cells: Vec<Coordinate> = lines.iter()
+    .flat_map(|line| (0..width as i32).map(move |x| Coordinate::new(x, *line as i32)))
+    .collect();
+
+  match effect {
+    ClearEffect::Flash => cells.into_iter().map(|cell| (cell, 1.0)).collect(),
+    ClearEffect::Wipe => {
+      // columns either side of the center go together
+      let center = (width as f32 - 1.0) / 2.0;
+      let steps = center.floor() + 1.0;
+      let mut order: Vec<(Coordinate, f32)> = cells.into_iter()
+        .map(|cell| (cell, ((cell.x as f32 - center).abs().floor() + 1.0) / steps))
+        .collect();
+      order.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+      order
+    }
+    ClearEffect::Dissolve => {
+      shuffle(&mut cells);
+      let count = cells.len() as f32;
+      cells.into_iter().enumerate().map(|(index, cell)| (cell, (index + 1) as f32 / count)).collect()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn flashes_tetrises_and_dissolves_t_spins() {
+    assert_eq!(ClearEffect::choose(MAX_LINES, false), ClearEffect::Flash);
+    assert_eq!(ClearEffect::choose(MAX_LINES, true), ClearEffect::Flash);
+    assert_eq!(ClearEffect::choose(2, true), ClearEffect::Dissolve);
+    assert_eq!(ClearEffect::choose(1, false), ClearEffect::Wipe);
+  }
+
+  #[test]
+  fn wipes_from_the_center_columns_out() {
+    let order = removal_order(ClearEffect::Wipe, &[38], 10);
+    let due = |x: i32| order.iter().find(|(cell, _)| cell.x == x).map(|(_, due)| *due).unwrap();
+
+    // the center pair go first and together, then each pair further out
+    assert_eq!(due(4), due(5));
+    assert!(order[..2].iter().all(|(cell, _)| cell.x == 4 || cell.x == 5));
+    for (inner, outer) in [(4, 3), (3, 2), (2, 1), (1, 0)] {
+      assert!(due(inner) < due(outer), "column {inner} is due after column {outer}");
+      assert_eq!(due(outer), due(9 - outer));
+    }
+
+    // an odd width starts from its single center column
+    let order = removal_order(ClearEffect::Wipe, &[38], 9);
+    assert_eq!(order[0].0.x, 4);
+    assert!(order[0].1 < order[1].1);
+  }
+
+  #[test]
+  fn removes_the_last_cell_of_every_effect_at_the_end() {
+    for effect in [ClearEffect::Flash, ClearEffect::Wipe, ClearEffect::Dissolve] {
+      let order = removal_order(effect, &[36, 38, 39], 10);
+      assert_eq!(order.len(), 30, "{effect:?}");
+      assert!(order.windows(2).all(|pair| pair[0].1 <= pair[1].1), "{effect:?} isn't soonest first");
+      assert_eq!(order.last().unwrap().1, 1.0, "{effect:?}");
+    }
+  }
+}
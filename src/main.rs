@@ -1,65 +1,133 @@
 #![windows_subsystem = "windows"]
 
+use std::env;
+use std::path::Path;
 use std::rc::Rc;
 
 use sdl2::keyboard::Keycode;
 
-use crate::algorithm::{calculate_score, calculate_speed_ms, determine_sfx};
-use crate::board::{Board, BoardEvent, BoardState};
-use crate::constants::game::{BORDER_COLOR, CLEAR_COOLDOWN, GAME_OVER_TEXT, GAME_PAUSED_TEXT, GAME_WON_TEXT, LEVEL_TEXT_POSITION, LINES_PER_LEVEL, LINES_TEXT_POSITION, MAX_TETRIS_LEVEL, MUSIC_VOLUME, NEXT_TEXT_POSITION, PREVIEW_BORDER, PREVIEW_DIMENSIONS, PREVIEW_POSITION, SCORE_TEXT_POSITION, SFX_VOLUME, SPAWN_COOLDOWN, START_TETRIS_LEVEL, STATE_TEXT_POSITION, STATISTICS_BORDER, TILE_SIZE};
-use crate::constants::window::{SCREEN_COLOR, SCREEN_PIXELS, TITLE, WINDOW_DIMENSIONS};
-use crate::engine::application::{Actions, run_application};
-use crate::engine::asset::{AssetManager, AssetType};
-use crate::engine::asset::audio::Loop;
-use crate::engine::asset::audio::SoundType;
-use crate::engine::event::EventStore;
-use crate::engine::geometry::Vec2;
-use crate::engine::render::{Properties, Renderer};
-use crate::engine::render::color::color;
-use crate::engine::render::text::Text;
-use crate::engine::tile::tilemap::Tilemap;
-use crate::engine::tile::tileset::Tileset;
-use crate::engine::time::{ConsumeAction, Timer};
-use crate::piece::{Piece, write_piece};
+use tetris::constants::controls::{PLAYER_ONE_CONTROLS, PLAYER_TWO_CONTROLS, RETRY_KEY, UNDO_KEY};
+use tetris::board::matrix_dimensions;
+use tetris::bot::{Bot, BotSettings, Difficulty};
+use tetris::constants::game::{BOARD_DIMENSIONS, BUFFER_ROWS, BOT_DIFFICULTY_TEXT, BOT_MENU_POSITION, BOT_SPEED_TEXT, BOT_SPEEDS, CONTINUE_TEXT, COPY_FUMEN_TEXT, DEFAULT_BOT_DIFFICULTY, DEFAULT_BOT_SPEED, DRILL_TEXT, EDITOR_TEXT, FUMEN_COPIED_TEXT, GAME_OVER_TEXT, GAME_PAUSED_TEXT, GAME_WON_TEXT, HEIGHT_TEXT, MARATHON_TEXT, MATRIX_DIMENSIONS, MAX_BOARD_DIMENSIONS, MENU_POSITION, HINT_TEXT, MIN_BOARD_DIMENSIONS, MUSIC_VOLUME, PAUSE_BLUR_RADIUS, PAUSE_MENU_POSITION, PAUSE_PANEL, PAUSE_PANEL_COLOR, PAUSE_PANEL_RADIUS, PLAYER_WON_TEXT, PRACTICE_TEXT, PUZZLE_FAILED_TEXT, PUZZLE_SOLVED_TEXT, PUZZLE_TEXT, PUZZLES_PATH, QUIT_TEXT, RESUME_TEXT, SFX_VOLUME, SHAKE_TEXT, SMOOTH_TEXT, SOLVED_MARKER, TILE_SIZE, START_TEXT, TITLE_TEXT_POSITION, TOGGLE_VALUES, VERSUS_BOT_TEXT, VERSUS_TEXT, WIDTH_TEXT};
+use tetris::constants::window::{APPLICATION, LETTERBOX_COLOR, ORGANIZATION, SCREEN_COLOR, SCREEN_PIXELS, TITLE, WINDOW_DIMENSIONS};
+use tetris::editor::{Editor, EditorEvent};
+use tetris::engine::application::{Actions, run_application};
+use tetris::engine::asset::{AssetManager, AssetType};
+use tetris::engine::asset::audio::Loop;
+use tetris::engine::asset::audio::SoundType;
+use tetris::engine::event::EventStore;
+use tetris::engine::geometry::Vec2;
+use tetris::engine::render::{Properties, Renderer, Scaling};
+use tetris::engine::render::color::color;
+use tetris::engine::render::text::Text;
+use tetris::engine::asset::texture::Texture;
+use tetris::engine::storage::user_path;
+use tetris::engine::time::timestamp;
+use tetris::engine::tile::tileset::Tileset;
+use tetris::fumen;
+use tetris::layout::{Layout, layout, use_layout};
+use tetris::menu::{Menu, MenuOption};
+use tetris::piece::check_pieces_fit;
+use tetris::piece_set::{piece_set, PieceSet, use_piece_set};
+use tetris::engine::utility::types::{Size, Size2};
+use tetris::player::{Controller, Player, player_pixels, PlayerEvent};
+use tetris::puzzle::{load_puzzles, Puzzle, PuzzleProgress};
+use tetris::save::{SavedGame, SavedMode};
+use tetris::settings::Settings;
+use tetris::tbp::external::ExternalBot;
 
 /**
  * Asset loading, main loop, and state management for the game.
  */
 
-mod engine;
-mod constants;
-mod piece;
-mod board;
-mod algorithm;
-
 #[derive(PartialEq, Clone)]
 enum GameState {
+  Title,
+  BotSetup,
+  PuzzleSelect,
+  Editor,
   Playing,
   GameOver,
   Won,
   Pause,
 }
 
+/// The ways to play
+#[derive(PartialEq, Clone, Copy)]
+enum Mode {
+  /// A single player clears lines until the final level
+  Marathon,
+  /// Two players send garbage to each other until one tops out
+  Versus,
+  /// A player sends garbage to a bot until one tops out
+  VersusBot(BotSettings),
+  /// A player places each piece at a target with the fewest inputs
+  Drill,
+  /// A player solves the puzzle at an index of the puzzle list
+  Puzzle(usize),
+  /// A single player clears lines from the fumen on the clipboard, or an empty board
+  Practice,
+  /// A single player clears lines from the board and queue built in the editor
+  Edited,
+}
+
+/// Modes listed on the title menu
+const MODES: [&str; 7] = [MARATHON_TEXT, VERSUS_TEXT, VERSUS_BOT_TEXT, DRILL_TEXT, PUZZLE_TEXT, PRACTICE_TEXT, EDITOR_TEXT];
+
+/// Option listed above the modes when there is a saved game
+const CONTINUE_OPTION: usize = 0;
+/// Options listed below the modes, counted from the first mode
+const WIDTH_OPTION: usize = MODES.len();
+const HEIGHT_OPTION: usize = MODES.len() + 1;
+
+/// Options listed on the bot setup menu
+const BOT_DIFFICULTY_OPTION: usize = 0;
+const BOT_SPEED_OPTION: usize = 1;
+const BOT_START_OPTION: usize = 2;
+
+/// Options listed on the pause menu
+const PAUSE_RESUME_OPTION: usize = 0;
+const PAUSE_HINT_OPTION: usize = 1;
+const PAUSE_SHAKE_OPTION: usize = 2;
+const PAUSE_SMOOTH_OPTION: usize = 3;
+const PAUSE_FUMEN_OPTION: usize = 4;
+const PAUSE_QUIT_OPTION: usize = 5;
+
+/// Arguments followed by the command of an external bot to play player one or two
+const BOT_ONE_ARG: &str = "--bot-one";
+const BOT_TWO_ARG: &str = "--bot-two";
+/// Argument followed by the path of a piece set file to play with instead of the standard Tetriminos
+const PIECE_SET_ARG: &str = "--piece-set";
+/// Argument followed by the path of a layout file to draw the board and HUD with instead of the standard layout
+const LAYOUT_ARG: &str = "--layout";
+
 // state
 struct Tetris {
-  board: Board,
   game_state: GameState,
-
-  preview: Tilemap,
-  next_text: Text,
-  state_text: Text,
-
-  level: u32,
-  score: u32,
-  lines: u32,
-  score_text: Text,
-  lines_text: Text,
-  level_text: Text,
-
-  spawn_cooldown: Timer,
-  drop_cooldown: Timer,
-
-  lines_to_clear: Vec<usize>,
+  mode: Mode,
+  tileset: Rc<Tileset>,
+
+  title_text: Text,
+  menu: Menu,
+  bot_menu: Menu,
+  pause_menu: Menu,
+  puzzle_menu: Menu,
+
+  puzzles: Vec<Puzzle>,
+  puzzle_progress: PuzzleProgress,
+  editor: Editor,
+  /// visible columns and rows of the boards of modes that can be resized
+  dimensions: Size2,
+  /// the unfinished game to continue, mirroring the save file
+  saved_game: Option<SavedGame>,
+  settings: Settings,
+  /// the game drawn behind the pause menu
+  pause_backdrop: Option<Rc<Texture>>,
+
+  players: Vec<Player>,
+  /// commands of external bots to play each player in place of the keyboard or built in bot
+  external_bots: [Option<String>; 2],
 }
 
 // Initialization //
@@ -68,6 +136,9 @@ fn load(assets: &mut AssetManager) {
   assets.load(AssetType::Texture, String::from("asset/spritesheet.png")).expect("failed to load texture");                                    // spritesheet
 
   assets.load(AssetType::Typeface { font_size: 5 }, String::from("asset/typeface.ttf")).expect("failed to load typeface");                    // pixel font
+  for font in &layout().fonts {
+    assets.typefaces.load_as(font.name.clone(), &font.path, font.size).expect("failed to load typeface");                                     // layout fonts
+  }
 
   assets.load(AssetType::Audio { sound_type: SoundType::Effect }, String::from("asset/level.ogg")).expect("failed to load sound effect");     // level advance
   assets.load(AssetType::Audio { sound_type: SoundType::Effect }, String::from("asset/line.ogg")).expect("failed to load sound effect");      // line clear
@@ -89,81 +160,369 @@ fn load(assets: &mut AssetManager) {
 fn setup(assets: &AssetManager) -> Tetris {
   let tileset = assets.tilesets.get("spritesheet").expect("failed to fetch tileset");
 
-  // create board
-  let mut board = Board::new(Rc::clone(&tileset));
+  let difficulties: Vec<&str> = Difficulty::ALL.iter().map(|difficulty| difficulty.name()).collect();
+  let speeds: Vec<&str> = BOT_SPEEDS.iter().map(|(name, _)| *name).collect();
+  let bot_options = vec![
+    MenuOption::choice(BOT_DIFFICULTY_TEXT, &difficulties, DEFAULT_BOT_DIFFICULTY),
+    MenuOption::choice(BOT_SPEED_TEXT, &speeds, DEFAULT_BOT_SPEED),
+    MenuOption::button(START_TEXT),
+  ];
+  let settings = Settings::load();
+  let pause_options = vec![
+    MenuOption::button(RESUME_TEXT),
+    MenuOption::choice(HINT_TEXT, &TOGGLE_VALUES, 0),
+    MenuOption::choice(SHAKE_TEXT, &TOGGLE_VALUES, settings.screen_shake as usize),
+    MenuOption::choice(SMOOTH_TEXT, &TOGGLE_VALUES, settings.smooth_fall as usize),
+    MenuOption::button(COPY_FUMEN_TEXT),
+    MenuOption::button(QUIT_TEXT),
+  ];
+  let puzzles = load_puzzles(Path::new(PUZZLES_PATH)).unwrap_or_else(|error| {
+    eprintln!("{error}");
+    Vec::new()
+  });
+  let editor = Editor::new(Rc::clone(&tileset));
+  let saved_game = SavedGame::load();
+
+  Tetris {
+    game_state: GameState::Title,
+    mode: Mode::Marathon,
+    tileset,
+
+    title_text: Text::new(String::from(TITLE), color::TEXT, TITLE_TEXT_POSITION),
+    menu: title_menu(saved_game.is_some(), BOARD_DIMENSIONS),
+    bot_menu: Menu::new(bot_options, BOT_MENU_POSITION),
+    pause_menu: Menu::new(pause_options, PAUSE_MENU_POSITION),
+    puzzle_menu: Menu::from_labels(&[], MENU_POSITION),
+
+    puzzles,
+    puzzle_progress: PuzzleProgress::load(),
+    editor,
+    dimensions: BOARD_DIMENSIONS,
+    saved_game,
+    settings,
+    pause_backdrop: None,
+
+    players: Vec::new(),
+    external_bots: read_external_bots(),
+  }
+}
+
+/// Read the external bot commands from the command line
+fn read_external_bots() -> [Option<String>; 2] {
+  let mut commands = [None, None];
+  let mut args = env::args().skip(1);
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      BOT_ONE_ARG => commands[0] = args.next(),
+      BOT_TWO_ARG => commands[1] = args.next(),
+      _ => {}
+    }
+  }
+  commands
+}
+
+/// Play with the piece set given on the command line, if there is one
+fn read_piece_set() -> Result<(), String> {
+  let mut args = env::args().skip(1);
+  while let Some(arg) = args.next() {
+    if arg == PIECE_SET_ARG {
+      let path = args.next().ok_or(format!("missing path for {PIECE_SET_ARG}"))?;
+      return use_piece_set(PieceSet::load(Path::new(&path))?);
+    }
+  }
+  Ok(())
+}
+
+/// Draw with the layout given on the command line, if there is one
+fn read_layout() -> Result<(), String> {
+  let mut args = env::args().skip(1);
+  while let Some(arg) = args.next() {
+    if arg == LAYOUT_ARG {
+      let path = args.next().ok_or(format!("missing path for {LAYOUT_ARG}"))?;
+      return use_layout(Layout::load(Path::new(&path))?);
+    }
+  }
+  Ok(())
+}
 
-  // create preview
-  let mut preview_board = Tilemap::new(tileset, PREVIEW_POSITION, PREVIEW_DIMENSIONS);
+/// Build the title menu, offering to continue above the modes if there is a saved game, and choosing board `dimensions` below them
+fn title_menu(continuable: bool, dimensions: Size2) -> Menu {
+  let mut options: Vec<MenuOption> = MODES.iter().map(|mode| MenuOption::button(mode)).collect();
+  if continuable {
+    options.insert(CONTINUE_OPTION, MenuOption::button(CONTINUE_TEXT));
+  }
 
-  // write preview
-  let BoardState { preview, .. } = board.next_piece();
-  write_preview(&mut preview_board, preview);
+  let (min, max) = (MIN_BOARD_DIMENSIONS, MAX_BOARD_DIMENSIONS);
+  let widths: Vec<String> = (min.x..=max.x).map(|width| width.to_string()).collect();
+  let heights: Vec<String> = (min.y..=max.y).map(|height| height.to_string()).collect();
+  let widths: Vec<&str> = widths.iter().map(String::as_str).collect();
+  let heights: Vec<&str> = heights.iter().map(String::as_str).collect();
+  options.push(MenuOption::choice(WIDTH_TEXT, &widths, (dimensions.x - min.x) as usize));
+  options.push(MenuOption::choice(HEIGHT_TEXT, &heights, (dimensions.y - min.y) as usize));
+
+  Menu::new(options, MENU_POSITION)
+}
+
+/// Show the title menu
+fn show_title(state: &mut Tetris) {
+  state.menu = title_menu(state.saved_game.is_some(), state.dimensions);
+  state.game_state = GameState::Title;
+}
+
+/// List the puzzles on the puzzle menu, marking the solved ones, and show it
+fn select_puzzle(state: &mut Tetris, renderer: &mut Renderer) {
+  let labels: Vec<String> = state.puzzles.iter()
+    .map(|puzzle| {
+      let marker = if state.puzzle_progress.is_solved(&puzzle.name) { SOLVED_MARKER } else { "" };
+      format!("{}{marker}", puzzle.name)
+    })
+    .collect();
+  let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+  state.puzzle_menu = Menu::from_labels(&labels, MENU_POSITION);
+
+  state.players.clear();
+  renderer.set_logical_size(SCREEN_PIXELS);
+  state.game_state = GameState::PuzzleSelect;
+}
+
+/// Build the players for `mode` and start playing
+fn start_game(state: &mut Tetris, mode: Mode, assets: &AssetManager, renderer: &mut Renderer) {
+  // fumens, puzzles, and the editor are laid out on a standard board
+  let dimensions = match mode {
+    Mode::Marathon | Mode::Versus | Mode::VersusBot(_) | Mode::Drill => state.dimensions,
+    Mode::Puzzle(_) | Mode::Practice | Mode::Edited => BOARD_DIMENSIONS,
+  };
+  if let Err(error) = check_pieces_fit(dimensions, BUFFER_ROWS) {
+    eprintln!("{error}");
+    show_title(state);
+    return;
+  }
+  add_players(state, mode, dimensions, BUFFER_ROWS);
+  match mode {
+    Mode::Drill => state.players[0].start_drill(),
+    Mode::Puzzle(index) => {
+      if let Err(error) = state.players[0].start_puzzle(&state.puzzles[index]) {
+        eprintln!("{error}");
+        select_puzzle(state, renderer);
+        return;
+      }
+    }
+    Mode::Practice => {
+      let text = renderer.clipboard_text().unwrap_or_default();
+      if !text.trim().is_empty() {
+        match fumen::decode(&text) {
+          Ok(pages) => state.players[0].start_fumen(&pages),
+          Err(error) => eprintln!("{error}"),
+        }
+      }
+    }
+    Mode::Edited => state.players[0].start_setup(&state.editor.board.cells(), state.editor.queue(), true),
+    _ => {}
+  }
+
+  // a new game replaces the saved one
+  if saved_mode(mode).is_some() {
+    discard_save(state);
+  }
+  play(state, assets, renderer);
+}
+
+/// Resume the saved game
+fn continue_game(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
+  let Some(saved) = state.saved_game.clone() else {
+    return;
+  };
+
+  let mode = match saved.mode {
+    SavedMode::Marathon => Mode::Marathon,
+    SavedMode::Practice => Mode::Practice,
+    SavedMode::Edited => Mode::Edited,
+  };
+  if let Err(error) = check_pieces_fit(saved.player.dimensions(), saved.player.buffer_rows()) {
+    eprintln!("{error}");
+    discard_save(state);
+    show_title(state);
+    return;
+  }
+  add_players(state, mode, saved.player.dimensions(), saved.player.buffer_rows());
+  if let Err(error) = state.players[0].load(&saved.player) {
+    eprintln!("{error}");
+    state.players.clear();
+    discard_save(state);
+    show_title(state);
+    return;
+  }
+  play(state, assets, renderer);
+}
+
+/// Build the players for `mode` with boards of `dimensions` below `buffer_rows` hidden rows
+fn add_players(state: &mut Tetris, mode: Mode, dimensions: Size2, buffer_rows: Size) {
+  state.mode = mode;
+  let (_, pieces_per_second) = BOT_SPEEDS[DEFAULT_BOT_SPEED];
+  let pieces_per_second = match mode {
+    Mode::VersusBot(settings) => settings.pieces_per_second,
+    _ => pieces_per_second,
+  };
+  // bots speak of standard boards and pieces only
+  let unplayable = if matrix_dimensions(dimensions, buffer_rows) != MATRIX_DIMENSIONS {
+    Some(format!("external bots only play {}x{} boards", BOARD_DIMENSIONS.x, BOARD_DIMENSIONS.y))
+  } else if !piece_set().is_standard() {
+    Some(String::from("external bots only play the standard pieces"))
+  } else {
+    None
+  };
+  let controller = |index: usize, fallback: Controller| match (&state.external_bots[index], &unplayable) {
+    (Some(command), None) => ExternalBot::launch(command, pieces_per_second)
+      .map(Controller::External)
+      .unwrap_or_else(|error| {
+        eprintln!("{error}");
+        fallback
+      }),
+    (Some(command), Some(reason)) => {
+      eprintln!("failed to launch {command}, {reason}");
+      fallback
+    }
+    (None, _) => fallback,
+  };
+
+  let player_two_offset = Vec2::new(player_pixels(dimensions).x as i32, 0);
+  let player_one = Player::new(Rc::clone(&state.tileset), controller(0, Controller::Keyboard(PLAYER_ONE_CONTROLS)), Vec2::default(), dimensions, buffer_rows);
+  let players = match mode {
+    Mode::Marathon | Mode::Drill | Mode::Puzzle(_) | Mode::Practice | Mode::Edited => vec![player_one],
+    Mode::Versus => vec![
+      player_one,
+      Player::new(Rc::clone(&state.tileset), controller(1, Controller::Keyboard(PLAYER_TWO_CONTROLS)), player_two_offset, dimensions, buffer_rows),
+    ],
+    Mode::VersusBot(settings) => vec![
+      player_one,
+      Player::new(Rc::clone(&state.tileset), controller(1, Controller::Bot(Bot::new(settings))), player_two_offset, dimensions, buffer_rows),
+    ],
+  };
+  state.players = players;
+  set_hints(state);
+  apply_settings(state);
+}
+
+/// Show the players and start playing the game they were built for
+fn play(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
+  if matches!(state.mode, Mode::Practice | Mode::Edited) {
+    state.players[0].start_practice();
+  }
+
+  // fit every player side by side
+  let pixels = player_pixels(state.players[0].board.dimensions());
+  renderer.set_logical_size(Vec2::new(pixels.x * state.players.len() as Size, pixels.y));
 
   // play music
   assets.audio.play("korobeiniki", MUSIC_VOLUME, Loop::Forever).expect("failed to play music");
 
-  Tetris {
-    game_state: GameState::Playing,
-    board,
-    preview: preview_board,
+  state.game_state = GameState::Playing;
+}
 
-    spawn_cooldown: Timer::new(SPAWN_COOLDOWN, false),
-    drop_cooldown: Timer::new(CLEAR_COOLDOWN, false),
-    lines_to_clear: Vec::new(),
+/// Leave the game and return to the menu it was started from
+fn leave_game(state: &mut Tetris, renderer: &mut Renderer) {
+  state.players.clear();
+  renderer.set_logical_size(SCREEN_PIXELS);
+  match state.mode {
+    Mode::Puzzle(_) => select_puzzle(state, renderer),
+    Mode::Edited => open_editor(state, renderer),
+    _ => show_title(state),
+  }
+}
 
-    level: START_TETRIS_LEVEL,
-    score: 0,
-    lines: 0,
-    score_text: Text::new(String::from("score 0000000"), color::TEXT, SCORE_TEXT_POSITION),
-    lines_text: Text::new(String::from("lines 0000000"), color::TEXT, LINES_TEXT_POSITION),
-    level_text: Text::new(String::from(format!("level {:0>7}", START_TETRIS_LEVEL)), color::TEXT, LEVEL_TEXT_POSITION),
-    next_text: Text::new(String::from("next"), color::TEXT, NEXT_TEXT_POSITION),
-    state_text: Text::new(String::from(""), color::TEXT, STATE_TEXT_POSITION),
+/// Get the mode a game of `mode` is saved as, if it can be saved
+fn saved_mode(mode: Mode) -> Option<SavedMode> {
+  match mode {
+    Mode::Marathon => Some(SavedMode::Marathon),
+    Mode::Practice => Some(SavedMode::Practice),
+    Mode::Edited => Some(SavedMode::Edited),
+    _ => None,
   }
 }
 
-// Rendering //
+/// Save the game to continue on the next launch, if its mode can be saved
+fn save_game(state: &mut Tetris) {
+  let Some(mode) = saved_mode(state.mode) else {
+    return;
+  };
 
-fn render(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
-  state.board.render(renderer, state.game_state == GameState::Playing);
+  let saved = SavedGame { mode, player: state.players[0].save() };
+  saved.save();
+  state.saved_game = Some(saved);
+}
 
-  state.state_text.render(&assets.typefaces.use_store().get("typeface").expect("failed to fetch typeface"), &assets.textures, renderer);
+/// Forget the saved game
+fn discard_save(state: &mut Tetris) {
+  if state.saved_game.take().is_some() {
+    SavedGame::delete();
+  }
+}
 
-  render_preview(&state.preview, assets, &mut state.next_text, renderer);
-  render_statistics(state, assets, renderer);
+/// Show the editor and the mouse cursor to paint with
+fn open_editor(state: &mut Tetris, renderer: &mut Renderer) {
+  renderer.set_cursor_visible(true);
+  state.game_state = GameState::Editor;
 }
 
-fn render_preview(preview: &Tilemap, assets: &AssetManager, text: &mut Text, renderer: &mut Renderer) {
-  // draw preview
-  for tile in preview {
-    if let Some(tile) = tile {
-      let position = Vec2::new(tile.position.x, tile.position.y);
-      renderer.draw_from_texture(&preview.tileset.texture, position, tile.src);
+// Rendering //
+
+fn render(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
+  if matches!(state.game_state, GameState::Title | GameState::BotSetup | GameState::PuzzleSelect) {
+    let typeface = assets.typefaces
+      .use_store()
+      .get("typeface")
+      .expect("failed to fetch typeface");
+    state.title_text.render(&typeface, &assets.textures, renderer);
+    match state.game_state {
+      GameState::BotSetup => state.bot_menu.render(assets, renderer),
+      GameState::PuzzleSelect => state.puzzle_menu.render(assets, renderer),
+      _ => state.menu.render(assets, renderer),
     }
+    return;
   }
 
-  // draw border
-  renderer.draw_rect(PREVIEW_BORDER, BORDER_COLOR);
+  if state.game_state == GameState::Editor {
+    state.editor.render(assets, renderer);
+    return;
+  }
+
+  if state.game_state == GameState::Pause {
+    render_pause(state, assets, renderer);
+    return;
+  }
 
-  // draw text
-  let typeface = assets.typefaces
-    .use_store()
-    .get("typeface")
-    .expect("failed to fetch typeface");
-  text.render(&typeface, &assets.textures, renderer);
+  let show_tiles = state.game_state == GameState::Playing;
+  for player in &mut state.players {
+    player.render(assets, renderer, show_tiles);
+  }
 }
 
-fn render_statistics(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
-  let typeface = assets.typefaces
-    .use_store()
-    .get("typeface")
-    .expect("failed to fetch typeface");
+/// Render the game blurred behind the pause menu
+fn render_pause(state: &mut Tetris, assets: &AssetManager, renderer: &mut Renderer) {
+  // the backdrop is rebuilt when the number of players or board size changes the resolution
+  let size = renderer.logical_size();
+  if state.pause_backdrop.as_ref().map(|backdrop| backdrop.dimensions) != Some(size) {
+    state.pause_backdrop = renderer.create_target(size).map(Rc::new).map_err(|error| eprintln!("{error}")).ok();
+  }
 
-  state.level_text.render(&typeface, &assets.textures, renderer);
-  state.score_text.render(&typeface, &assets.textures, renderer);
-  state.lines_text.render(&typeface, &assets.textures, renderer);
+  match &state.pause_backdrop {
+    Some(backdrop) => {
+      renderer.with_target(backdrop, |renderer| {
+        for player in &mut state.players {
+          player.render(assets, renderer, false);
+        }
+      });
+      renderer.draw_texture_blurred(backdrop, Vec2::new(0, 0), PAUSE_BLUR_RADIUS);
+    }
+    None => {
+      for player in &mut state.players {
+        player.render(assets, renderer, false);
+      }
+    }
+  }
 
-  renderer.draw_rect(STATISTICS_BORDER, BORDER_COLOR);
+  renderer.fill_rounded_rect(PAUSE_PANEL, PAUSE_PANEL_RADIUS, PAUSE_PANEL_COLOR);
+  state.pause_menu.render(assets, renderer);
 }
 
 // Update //
@@ -174,13 +533,16 @@ fn update(events: &EventStore, assets: &AssetManager, state: &mut Tetris, render
     match state.game_state {
       GameState::Playing => {
         pause_sound(assets);
-        state.state_text.set_content(String::from(GAME_PAUSED_TEXT));
+        set_state_text(&mut state.players, GAME_PAUSED_TEXT);
+        save_game(state);
+        state.pause_menu.slide_in();
         state.game_state = GameState::Pause;
       }
-      GameState::Pause => {
-        pause_sound(assets);
-        state.state_text.clear_content();
-        state.game_state = GameState::Playing;
+      GameState::Pause => resume(state, assets),
+      GameState::BotSetup | GameState::PuzzleSelect => show_title(state),
+      GameState::Editor => {
+        renderer.set_cursor_visible(false);
+        show_title(state);
       }
       _ => {}
     };
@@ -191,119 +553,229 @@ fn update(events: &EventStore, assets: &AssetManager, state: &mut Tetris, render
     renderer.set_fullscreen(!renderer.is_fullscreen());
   }
 
-  match &state.game_state {
-    GameState::Playing => {
-      match state.board.update(events) {
-        BoardEvent::MoveLeft | BoardEvent::MoveRight => {
-          // play sound effect
-          assets.audio.play("move", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-        }
-        BoardEvent::Rotate => {
-          // play sound effect
-          assets.audio.play("rotate", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-        }
-        BoardEvent::Land => {
-          // play sound effect
-          assets.audio.play("land", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-
-          // delete active piece
-          state.board.kill_piece();
-
-          // check for full lines
-          state.lines_to_clear = state.board.get_full_lines();
-          let lines_cleared = state.lines_to_clear.len() as u32;
-          if lines_cleared > 0 {
-            state.lines += lines_cleared;
-            state.lines_text.set_content(format!("LINES {:0>7}", state.lines));
-
-            if let Some(clear_line_sfx) = determine_sfx(lines_cleared) {
-              assets.audio.play(clear_line_sfx, SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-            }
+  // switch between pixel perfect and fit to window scaling
+  if events.is_key_pressed(Keycode::F10) {
+    let scaling = match renderer.scaling() {
+      Scaling::Integer => Scaling::Fit,
+      Scaling::Fit => Scaling::Integer,
+    };
+    renderer.set_scaling(scaling);
+    state.settings.scaling = scaling;
+    state.settings.save();
+  }
 
-            // clear lines
-            for line in &state.lines_to_clear {
-              state.board.clear_line(*line).expect("failed to clear line");
-            }
+  // remember where the window was left, saved when closing
+  if !renderer.is_fullscreen() {
+    if let Some(size) = events.window_resized() {
+      state.settings.window_size = Some(size);
+    }
+    if let Some(position) = events.window_moved() {
+      state.settings.window_position = Some(position);
+    }
+  }
 
-            // start the drop cooldown
-            state.drop_cooldown.start();
-          } else {
-            // no lines to clear, start the spawn cooldown
-            state.spawn_cooldown.start();
-          }
-        }
-        _ => {}
-      }
+  // save a screenshot of the next frame
+  if events.is_key_pressed(Keycode::F12) {
+    match user_path(ORGANIZATION, APPLICATION, &format!("screenshot-{}.png", timestamp())) {
+      Ok(path) => renderer.request_screenshot(path),
+      Err(error) => eprintln!("{error}"),
+    }
+  }
 
-      // check if the drop cooldown is done
-      if state.drop_cooldown.consume(ConsumeAction::Disable) {
-        // get full lines
-        let lines_cleared = state.lines_to_clear.len() as u32;
-        if lines_cleared > 0 {
-          // drop full lines
-          for line in &state.lines_to_clear {
-            state.board.move_lines_down(*line).expect("failed to clear line");
+  match &state.game_state {
+    GameState::Title => {
+      let continuable = state.saved_game.is_some();
+      let selected = state.menu.update(events);
+      let offset = continuable as usize;
+      let width = MIN_BOARD_DIMENSIONS.x + state.menu.value(WIDTH_OPTION + offset) as Size;
+      let height = MIN_BOARD_DIMENSIONS.y + state.menu.value(HEIGHT_OPTION + offset) as Size;
+      state.dimensions = Vec2::new(width, height);
+      match selected {
+        Some(CONTINUE_OPTION) if continuable => continue_game(state, assets, renderer),
+        // the modes follow the continue option when it is listed
+        Some(option) => match option - offset {
+          0 => start_game(state, Mode::Marathon, assets, renderer),
+          1 => start_game(state, Mode::Versus, assets, renderer),
+          2 => {
+            state.bot_menu.slide_in();
+            state.game_state = GameState::BotSetup;
           }
-          state.lines_to_clear.clear(); // done
-
-          // calculate score
-          let points = calculate_score(lines_cleared, state.level).expect("failed to calculate score");
-          state.score += points;
-          state.score_text.set_content(format!("SCORE {:0>7}", state.score));
-
-          // check level advance
-          if state.lines >= state.level * LINES_PER_LEVEL {
-            state.level += 1;
-            state.level_text.set_content(format!("LEVEL {:0>7}", state.level));
-
-            if state.level <= MAX_TETRIS_LEVEL {
-              let new_speed = calculate_speed_ms(state.level).expect("failed to calculate speed");
-              state.board.set_speed_ms(new_speed);
-              assets.audio.play("level", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-            } else {
-              state.game_state = GameState::Won;
+          3 => start_game(state, Mode::Drill, assets, renderer),
+          4 => select_puzzle(state, renderer),
+          5 => start_game(state, Mode::Practice, assets, renderer),
+          6 => open_editor(state, renderer),
+          _ => {}
+        },
+        None => {}
+      }
+    }
+    GameState::BotSetup => {
+      if state.bot_menu.update(events) == Some(BOT_START_OPTION) {
+        let (_, pieces_per_second) = BOT_SPEEDS[state.bot_menu.value(BOT_SPEED_OPTION)];
+        let difficulty = Difficulty::ALL[state.bot_menu.value(BOT_DIFFICULTY_OPTION)];
+        start_game(state, Mode::VersusBot(BotSettings { pieces_per_second, difficulty }), assets, renderer);
+      }
+    }
+    GameState::Editor => {
+      if state.editor.update(events) == EditorEvent::Play {
+        renderer.set_cursor_visible(false);
+        start_game(state, Mode::Edited, assets, renderer);
+      }
+    }
+    GameState::PuzzleSelect => {
+      if let Some(index) = state.puzzle_menu.update(events) {
+        start_game(state, Mode::Puzzle(index), assets, renderer);
+      }
+    }
+    GameState::Playing => {
+      rewind(state, events);
+      for index in 0..state.players.len() {
+        match state.players[index].update(events, assets) {
+          PlayerEvent::Attack { lines } => {
+            // send garbage to every opponent
+            if matches!(state.mode, Mode::Versus | Mode::VersusBot(_)) {
+              for (_, opponent) in state.players.iter_mut().enumerate().filter(|(other, _)| *other != index) {
+                opponent.garbage.push(lines);
+              }
             }
           }
+          PlayerEvent::ToppedOut(_) => {
+            end_game(state, assets, index, false);
+            break;
+          }
+          PlayerEvent::Won => {
+            end_game(state, assets, index, true);
+            break;
+          }
+          PlayerEvent::Nothing => {}
         }
-
-        // drop sfx
-        assets.audio.play("shift", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
-
-        // start the spawn cooldown
-        state.spawn_cooldown.start();
       }
-
-      // check if the spawn cooldown is done
-      if state.spawn_cooldown.consume(ConsumeAction::Disable) {
-        state.game_state = next_state(&mut state.board, &mut state.preview);
-        if state.game_state == GameState::GameOver {
+    }
+    GameState::Pause => {
+      match state.pause_menu.update(events) {
+        Some(PAUSE_RESUME_OPTION) => resume(state, assets),
+        Some(PAUSE_FUMEN_OPTION) => {
+          match state.players[0].fumen().and_then(|fumen| renderer.set_clipboard_text(&fumen)) {
+            Ok(()) => state.players[0].state_text.set_content(String::from(FUMEN_COPIED_TEXT)),
+            Err(error) => eprintln!("{error}"),
+          }
+        }
+        Some(PAUSE_QUIT_OPTION) => {
           assets.audio.stop("korobeiniki").expect("failed to stop music");
-          assets.audio.play("gameover", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+          leave_game(state, renderer);
         }
+        _ => {}
       }
     }
-    GameState::Won => {
-      state.state_text.set_content(String::from(GAME_WON_TEXT));
-    }
-    GameState::GameOver => {
-      state.state_text.set_content(String::from(GAME_OVER_TEXT));
+    GameState::GameOver | GameState::Won => {
+      if rewind(state, events) {
+        // keep practicing from before the game ended
+        state.players[0].state_text.clear_content();
+        assets.audio.play("korobeiniki", MUSIC_VOLUME, Loop::Forever).expect("failed to play music");
+        state.game_state = GameState::Playing;
+      } else if events.is_key_pressed(Keycode::Return) {
+        leave_game(state, renderer);
+      }
     }
-    _ => {}
   }
 }
 
-fn write_preview(preview: &mut Tilemap, piece: &Piece) {
-  preview.clear_tiles();
-  write_piece(piece, preview);
-}
+/// Undo the last placement or retry from the start with the practice keys in `events`
+///
+/// returns `true` if the board was rewound
+fn rewind(state: &mut Tetris, events: &EventStore) -> bool {
+  if !matches!(state.mode, Mode::Practice | Mode::Edited) {
+    return false;
+  }
 
-fn next_state(board: &mut Board, preview_board: &mut Tilemap) -> GameState {
-  let BoardState { preview, space, .. } = board.next_piece();
-  write_preview(preview_board, preview);
-  if space {
-    GameState::Playing
+  let player = &mut state.players[0];
+  if events.is_key_pressed(UNDO_KEY) {
+    player.undo()
+  } else if events.is_key_pressed(RETRY_KEY) {
+    player.retry()
   } else {
-    GameState::GameOver
+    false
+  }
+}
+
+/// Leave the pause menu and continue playing
+fn resume(state: &mut Tetris, assets: &AssetManager) {
+  pause_sound(assets);
+  for player in &mut state.players {
+    player.state_text.clear_content();
+  }
+  set_hints(state);
+  apply_settings(state);
+  state.game_state = GameState::Playing;
+}
+
+/// Show or hide the hint for every player as chosen on the pause menu
+fn set_hints(state: &mut Tetris) {
+  let show = state.pause_menu.value(PAUSE_HINT_OPTION) == 1;
+  for player in &mut state.players {
+    player.set_hint(show);
+  }
+}
+
+/// Apply the settings chosen on the pause menu to every player, saving them if they changed
+fn apply_settings(state: &mut Tetris) {
+  let screen_shake = state.pause_menu.value(PAUSE_SHAKE_OPTION) == 1;
+  let smooth_fall = state.pause_menu.value(PAUSE_SMOOTH_OPTION) == 1;
+  if (screen_shake, smooth_fall) != (state.settings.screen_shake, state.settings.smooth_fall) {
+    state.settings.screen_shake = screen_shake;
+    state.settings.smooth_fall = smooth_fall;
+    state.settings.save();
+  }
+  for player in &mut state.players {
+    player.set_screen_shake(screen_shake);
+    player.set_smooth_fall(smooth_fall);
+  }
+}
+
+/// End the game after the player at `index` has `won` or topped out
+fn end_game(state: &mut Tetris, assets: &AssetManager, index: usize, won: bool) {
+  assets.audio.stop("korobeiniki").expect("failed to stop music");
+
+  match state.mode {
+    Mode::Marathon | Mode::Drill | Mode::Practice | Mode::Edited => {
+      discard_save(state);
+      if won {
+        set_state_text(&mut state.players, GAME_WON_TEXT);
+        state.game_state = GameState::Won;
+      } else {
+        assets.audio.play("gameover", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+        set_state_text(&mut state.players, GAME_OVER_TEXT);
+        state.game_state = GameState::GameOver;
+      }
+    }
+    Mode::Puzzle(puzzle) => {
+      if won {
+        state.puzzle_progress.solve(&state.puzzles[puzzle].name);
+        set_state_text(&mut state.players, PUZZLE_SOLVED_TEXT);
+        state.game_state = GameState::Won;
+      } else {
+        assets.audio.play("gameover", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+        set_state_text(&mut state.players, PUZZLE_FAILED_TEXT);
+        state.game_state = GameState::GameOver;
+      }
+    }
+    Mode::Versus | Mode::VersusBot(_) => {
+      assets.audio.play("gameover", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+      for (other, player) in state.players.iter_mut().enumerate() {
+        // the player who won, or the opponent of the player who topped out, is the winner
+        let winner = (other == index) == won;
+        let text = if winner { PLAYER_WON_TEXT } else { GAME_OVER_TEXT };
+        player.state_text.set_content(String::from(text));
+      }
+      state.game_state = GameState::GameOver;
+    }
+  }
+}
+
+/// Set the state text of every player
+fn set_state_text(players: &mut [Player], text: &str) {
+  for player in players {
+    player.state_text.set_content(String::from(text));
   }
 }
 
@@ -311,25 +783,41 @@ fn pause_sound(assets: &AssetManager) {
   assets.audio.play("pause", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
 }
 
+/// Save the game in play and where the window was left before the window closes
+fn quit(state: &mut Tetris) {
+  if matches!(state.game_state, GameState::Playing | GameState::Pause) {
+    save_game(state);
+  }
+  state.settings.save();
+}
+
 // Main //
 
 pub fn main() -> Result<(), String> {
+  read_piece_set()?;
+  read_layout()?;
+  let settings = Settings::load();
   run_application(
     Properties {
       title: String::from(TITLE),
-      dimensions: WINDOW_DIMENSIONS,
+      dimensions: settings.window_size.unwrap_or(WINDOW_DIMENSIONS),
+      position: settings.window_position,
       logical: Some(SCREEN_PIXELS),
+      scaling: settings.scaling,
       fullscreen: false,
+      resizable: true,
       show_cursor: false,
       vsync: true,
       opengl: true,
       software_acceleration: false,
       hardware_acceleration: true,
       screen_color: SCREEN_COLOR,
+      letterbox_color: LETTERBOX_COLOR,
     }, Actions {
       load,
       render,
       update,
       setup,
+      quit,
     })
 }
@@ -0,0 +1,156 @@
+use sdl2::keyboard::Keycode;
+
+use crate::constants::game::{MENU_SLIDE_DISTANCE, MENU_SLIDE_DURATION, MENU_SPACING};
+use crate::engine::asset::AssetManager;
+use crate::engine::event::EventStore;
+use crate::engine::geometry::Vec2;
+use crate::engine::render::color::color;
+use crate::engine::render::Renderer;
+use crate::engine::render::text::Text;
+use crate::engine::tween::{Easing, Tween};
+
+/**
+ * A vertical list of selectable options
+ */
+
+const CURSOR: &str = "> ";
+const NO_CURSOR: &str = "  ";
+
+/// An entry in a menu
+pub struct MenuOption {
+  label: String,
+  /// values the option can cycle through, empty if the option is a button
+  values: Vec<String>,
+  value: usize,
+}
+
+impl MenuOption {
+  /// Instantiate an option that is chosen with confirm
+  pub fn button(label: &str) -> Self {
+    Self { label: String::from(label), values: Vec::new(), value: 0 }
+  }
+
+  /// Instantiate an option that cycles through `values` starting at `value`
+  pub fn choice(label: &str, values: &[&str], value: usize) -> Self {
+    let values = values.iter().map(|value| String::from(*value)).collect();
+    Self { label: String::from(label), values, value }
+  }
+
+  /// Format the option for display
+  fn format(&self, selected: bool) -> String {
+    let prefix = if selected { CURSOR } else { NO_CURSOR };
+    match self.values.get(self.value) {
+      Some(value) => format!("{prefix}{} <{value}>", self.label),
+      None => format!("{prefix}{}", self.label),
+    }
+  }
+}
+
+/// A list of options navigated with the keyboard
+pub struct Menu {
+  options: Vec<MenuOption>,
+  texts: Vec<Text>,
+  selected: usize,
+  position: Vec2<i32>,
+  /// horizontal offset of the options as they slide in
+  slide: Tween<i32>,
+}
+
+impl Menu {
+  /// Instantiate a new menu of `options` listed down from `position`
+  pub fn new(options: Vec<MenuOption>, position: Vec2<i32>) -> Self {
+    let texts = options.iter()
+      .enumerate()
+      .map(|(index, option)| Text::new(option.format(index == 0), color::TEXT, option_position(position, index)))
+      .collect();
+
+    Self { options, texts, selected: 0, position, slide: slide_in() }
+  }
+
+  /// Instantiate a new menu of buttons labeled `labels` listed down from `position`
+  pub fn from_labels(labels: &[&str], position: Vec2<i32>) -> Self {
+    let options = labels.iter().map(|label| MenuOption::button(label)).collect();
+    Self::new(options, position)
+  }
+
+  /// Move the cursor and cycle values with `events`
+  ///
+  /// returns the index of the option confirmed this frame, if any
+  pub fn update(&mut self, events: &EventStore) -> Option<usize> {
+    if self.options.is_empty() {
+      return None;
+    }
+
+    let count = self.options.len();
+    if events.is_key_pressed(Keycode::W) || events.is_key_pressed(Keycode::Up) {
+      self.select((self.selected + count - 1) % count);
+    }
+    if events.is_key_pressed(Keycode::S) || events.is_key_pressed(Keycode::Down) {
+      self.select((self.selected + 1) % count);
+    }
+
+    let values = self.options[self.selected].values.len();
+    if values > 0 {
+      if events.is_key_pressed(Keycode::A) || events.is_key_pressed(Keycode::Left) {
+        self.cycle(values - 1);
+      }
+      if events.is_key_pressed(Keycode::D) || events.is_key_pressed(Keycode::Right) {
+        self.cycle(1);
+      }
+    }
+
+    if events.is_key_pressed(Keycode::J) || events.is_key_pressed(Keycode::Return) {
+      return Some(self.selected);
+    }
+    None
+  }
+
+  /// Get the index of the value chosen for the option at `index`
+  pub fn value(&self, index: usize) -> usize {
+    self.options.get(index).map_or(0, |option| option.value)
+  }
+
+  /// Slide the options in from the left again
+  pub fn slide_in(&mut self) {
+    self.slide = slide_in();
+  }
+
+  /// Move the cursor to the option at `index`
+  fn select(&mut self, index: usize) {
+    let previous = self.selected;
+    self.selected = index;
+    self.texts[previous].set_content(self.options[previous].format(false));
+    self.texts[index].set_content(self.options[index].format(true));
+  }
+
+  /// Advance the value of the selected option by `step`
+  fn cycle(&mut self, step: usize) {
+    let option = &mut self.options[self.selected];
+    option.value = (option.value + step) % option.values.len();
+    self.texts[self.selected].set_content(option.format(true));
+  }
+
+  /// Render the options
+  pub fn render(&mut self, assets: &AssetManager, renderer: &mut Renderer) {
+    let typeface = assets.typefaces
+      .use_store()
+      .get("typeface")
+      .expect("failed to fetch typeface");
+
+    let offset = Vec2::new(self.slide.update(), 0);
+    for (index, text) in self.texts.iter_mut().enumerate() {
+      text.set_position(option_position(self.position, index) + offset);
+      text.render(&typeface, &assets.textures, renderer);
+    }
+  }
+}
+
+/// Get the position of the option at `index` of a menu listed down from `position`
+fn option_position(position: Vec2<i32>, index: usize) -> Vec2<i32> {
+  position + Vec2::new(0, index as i32 * MENU_SPACING)
+}
+
+/// Ease the options from the left to their place
+fn slide_in() -> Tween<i32> {
+  Tween::new(-MENU_SLIDE_DISTANCE, 0, MENU_SLIDE_DURATION, Easing::CubicOut)
+}
@@ -1,11 +1,11 @@
-use crate::algorithm::{check_bounds, check_shape_collision, get_new_shape_coordinates, is_shape_on_bottom, transform_shape};
+use crate::algorithm::{check_bounds, check_shape_collision, get_new_shape_coordinates, Grid, is_shape_on_bottom, transform_shape};
 use crate::constants::game::{PLAYER_DROP_COOLDOWN, PLAYER_SLIDE_COOLDOWN};
-use crate::constants::piece::{DEFAULT_ROTATION, Shape, ShapeData, ShapeType};
+use crate::constants::piece::{DEFAULT_ROTATION, PieceData, Shape, ShapeData, ShapeType};
 use crate::engine::geometry::Vec2;
 use crate::engine::tile::{tile::TileData, tilemap::Tilemap};
 use crate::engine::tile::tileset::Tileset;
 use crate::engine::time::Timer;
-use crate::engine::utility::types::Coordinate;
+use crate::engine::utility::types::{Coordinate, Size, Size2};
 
 #[derive(Debug, PartialEq)]
 pub enum PieceState {
@@ -24,6 +24,8 @@ pub struct Piece {
 
   pub rotation: usize,
   pub position: Coordinate,
+  /// the last successful move was a rotation
+  pub spun: bool,
 
   pub player_slide_cooldown: Timer,
   pub player_drop_cooldown: Timer,
@@ -38,10 +40,11 @@ impl Piece {
       shape_type,
       state: PieceState::Active,
       tile_data,
-      shape_data: piece_data.shape,
+      shape_data: piece_data.shape.clone(),
 
       rotation: DEFAULT_ROTATION,
       position: Vec2::new(0, 0),
+      spun: false,
 
       player_slide_cooldown: Timer::new(PLAYER_SLIDE_COOLDOWN, true),
       player_drop_cooldown: Timer::new(PLAYER_DROP_COOLDOWN, true),
@@ -49,6 +52,46 @@ impl Piece {
   }
 }
 
+/// Get the position `shape_type` spawns at on a board `width` wide, resting on the hidden row above `first_row`, the first
+/// visible row
+///
+/// Pieces spawn left of center, moved left if they would overhang the right wall but no further than the left wall, then
+/// moved by their spawn offset.
+pub fn spawn_position(shape_type: &ShapeType, width: Size, first_row: i32) -> Coordinate {
+  let piece_data = shape_type.data();
+  let shape = &piece_data.shape[DEFAULT_ROTATION];
+  let right = shape.iter().map(|coord| coord.x).max().unwrap_or_default();
+  let bottom = shape.iter().map(|coord| coord.y).max().unwrap_or_default();
+  let x = (width as i32 / 2 - 1).min(width as i32 - 1 - right).max(0);
+  Coordinate::new(x, first_row - 1 - bottom) + piece_data.spawn_offset
+}
+
+/// Check that every piece in play fits on a board of `dimensions` visible columns and rows below `buffer_rows` hidden
+/// rows, spawning on the board and turning to each rotation within its walls
+pub fn check_pieces_fit(dimensions: Size2, buffer_rows: Size) -> Result<(), String> {
+  let (width, height) = dimensions.destructure();
+  for shape_type in ShapeType::all() {
+    let piece_data = shape_type.data();
+    let widest = piece_data.shape.iter()
+      .map(|shape| {
+        let (left, right) = shape.iter().fold((i32::MAX, i32::MIN), |(left, right), coord| (left.min(coord.x), right.max(coord.x)));
+        (right - left + 1) as Size
+      })
+      .max()
+      .unwrap_or_default();
+    if widest > width {
+      return Err(format!("piece {} is {widest} wide, too wide for a board {width} wide", shape_type.name()));
+    }
+
+    let position = spawn_position(&shape_type, width, buffer_rows as i32);
+    let spawn = transform_shape(&piece_data.shape[DEFAULT_ROTATION], &position);
+    if spawn.iter().any(|coord| coord.x < 0 || coord.x >= width as i32 || coord.y >= (height + buffer_rows) as i32) {
+      return Err(format!("piece {} spawns off a board {width}x{height}", shape_type.name()));
+    }
+  }
+  Ok(())
+}
+
 // writers //
 
 pub fn write_piece(piece: &Piece, tilemap: &mut Tilemap) {
@@ -67,7 +110,7 @@ pub fn erase_piece(piece: &Piece, tilemap: &mut Tilemap) {
 
 // Transform //
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Transform { Left, Right, Down }
 
 impl Transform {
@@ -90,19 +133,19 @@ pub enum TransformResult {
   Land,
 }
 
-/// Check if the piece can be transformed
-fn evaluate_transform(piece: &Piece, transform: Transform, tilemap: &Tilemap) -> TransformResult {
-  let shape = &piece.shape_data[piece.rotation];
+/// Check if `shape` at `position` can be transformed
+pub fn evaluate_transform(shape: &Shape, position: &Coordinate, transform: Transform, grid: &impl Grid) -> TransformResult {
   let transform_coord = transform.to_coordinate();
-  let new_position = Coordinate::new(piece.position.x + transform_coord.x, piece.position.y + transform_coord.y);
-  let new_shape = transform_shape(&shape, &new_position);
+  let new_position = Coordinate::new(position.x + transform_coord.x, position.y + transform_coord.y);
+  let old_shape = transform_shape(shape, position);
+  let new_shape = transform_shape(shape, &new_position);
 
   // check bounds
-  let unchecked_coordinates: Shape = get_new_shape_coordinates(shape, &new_shape);
-  let is_bound = check_bounds(&unchecked_coordinates, tilemap);
+  let unchecked_coordinates: Shape = get_new_shape_coordinates(&old_shape, &new_shape);
+  let is_bound = check_bounds(&unchecked_coordinates, grid);
 
   if !is_bound {
-    let on_bottom = is_shape_on_bottom(&unchecked_coordinates, tilemap);
+    let on_bottom = is_shape_on_bottom(&unchecked_coordinates, grid);
     if transform == Transform::Down && on_bottom {
       // if we're moving down and out of bounds, we've landed
       return TransformResult::Land;
@@ -111,7 +154,7 @@ fn evaluate_transform(piece: &Piece, transform: Transform, tilemap: &Tilemap) ->
   }
 
   // check shape collision
-  let is_collision = check_shape_collision(&unchecked_coordinates, tilemap);
+  let is_collision = check_shape_collision(&unchecked_coordinates, grid);
   if is_collision {
     // if we're moving down and there's a collision, we've landed
     if transform == Transform::Down {
@@ -125,10 +168,11 @@ fn evaluate_transform(piece: &Piece, transform: Transform, tilemap: &Tilemap) ->
 
 /// Apply the transform to the piece if possible
 pub fn transform_piece(piece: &mut Piece, event: Transform, tilemap: &mut Tilemap) -> PieceState {
-  match evaluate_transform(piece, event, tilemap) {
+  match evaluate_transform(&piece.shape_data[piece.rotation], &piece.position, event, tilemap) {
     TransformResult::Success { position } => {
       piece.position = position;
       piece.state = PieceState::Active;
+      piece.spun = false;
       PieceState::Active
     }
     TransformResult::Land => {
@@ -144,45 +188,59 @@ pub fn transform_piece(piece: &mut Piece, event: Transform, tilemap: &mut Tilema
 // Rotation //
 
 pub enum RotationResult {
-  /// The piece can be rotated.
-  Success { rotation: usize },
+  /// The piece can be rotated, kicked to `position`.
+  Success { rotation: usize, position: Coordinate },
   /// The piece is unable to move due to a collision with shape or bounds.
   Collision,
 }
 
-/// Check if the piece can be rotated
-fn evaluate_rotation(piece: &Piece, tilemap: &Tilemap) -> RotationResult {
-  let shape = &piece.shape_data[piece.rotation];
-  let shape_rotations = piece.shape_type.data().shape;
-
-  // get new shape
-  let new_rotation = (piece.rotation + 1) % shape_rotations.len();
-  let new_shape: Shape = shape_rotations[new_rotation].iter()
-    .map(|coord| Coordinate::new(coord.x + piece.position.x, coord.y + piece.position.y))
-    .collect();
-
-  // check bounds
-  let unchecked_coordinates: Shape = get_new_shape_coordinates(&shape, &new_shape);
-  let is_bound = check_bounds(&unchecked_coordinates, tilemap);
-  if !is_bound {
-    return RotationResult::Collision;
-  }
-
-  // check shape collision
-  let is_collision = check_shape_collision(&unchecked_coordinates, tilemap);
-  if is_collision {
-    return RotationResult::Collision;
+/// Check if `piece` at `position` can be rotated from `rotation`, trying each of its kicks in turn
+pub fn evaluate_rotation(piece: &PieceData, rotation: usize, position: &Coordinate, grid: &impl Grid) -> RotationResult {
+  let shape = transform_shape(&piece.shape[rotation], position);
+  let new_rotation = (rotation + 1) % piece.shape.len();
+
+  for kick in piece.kicks(rotation) {
+    // get new shape
+    let new_position = *position + *kick;
+    let new_shape = transform_shape(&piece.shape[new_rotation], &new_position);
+
+    // check bounds and shape collision
+    let unchecked_coordinates: Shape = get_new_shape_coordinates(&shape, &new_shape);
+    if check_bounds(&unchecked_coordinates, grid) && !check_shape_collision(&unchecked_coordinates, grid) {
+      return RotationResult::Success {
+        rotation: new_rotation,
+        position: new_position,
+      };
+    }
   }
 
-  RotationResult::Success {
-    rotation: new_rotation,
-  }
+  RotationResult::Collision
 }
 
 /// Apply the rotation to the piece if possible
 pub fn rotate_piece(piece: &mut Piece, tilemap: &mut Tilemap) -> PieceState {
-  if let RotationResult::Success { rotation } = evaluate_rotation(piece, tilemap) {
+  if let RotationResult::Success { rotation, position } = evaluate_rotation(piece.shape_type.data(), piece.rotation, &piece.position, tilemap) {
     piece.rotation = rotation;
+    piece.position = position;
+    piece.spun = true;
   }
   return PieceState::Active; // shape is always active after rotation
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::constants::game::{BOARD_DIMENSIONS, BUFFER_ROWS, FIRST_ROW};
+
+  #[test]
+  fn spawns_within_the_left_wall_of_a_narrow_board() {
+    assert_eq!(spawn_position(&ShapeType::I, 3, FIRST_ROW).x, 0);
+  }
+
+  #[test]
+  fn checks_the_pieces_fit_the_board() {
+    assert!(check_pieces_fit(BOARD_DIMENSIONS, BUFFER_ROWS).is_ok());
+    assert!(check_pieces_fit(Vec2::new(4, 10), BUFFER_ROWS).is_ok());
+    assert!(check_pieces_fit(Vec2::new(3, 10), BUFFER_ROWS).is_err());
+  }
+}
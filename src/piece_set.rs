@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::constants::game::{GARBAGE_TILE_ID, PREVIEW_DIMENSIONS, STANDARD_PIECE_SET};
+use crate::constants::piece::{DEFAULT_ROTATION, parse_shape, PieceData, standard_pieces};
+use crate::engine::storage::read_json;
+use crate::engine::tile::tile::TileId;
+use crate::engine::utility::types::Coordinate;
+
+/**
+ * The pieces played with, the standard Tetriminos or a set read from a file
+ */
+
+static STANDARD_SET: OnceLock<PieceSet> = OnceLock::new();
+static PIECE_SET: OnceLock<PieceSet> = OnceLock::new();
+
+/// A piece as written in a piece set file
+#[derive(Deserialize)]
+struct RawPiece {
+  name: String,
+  tile: TileId,
+  /// the rows of each rotation, top down, where `#` is a tile
+  rotations: Vec<Vec<String>>,
+  #[serde(default)]
+  spawn_offset: Coordinate,
+  #[serde(default)]
+  preview_offset: Coordinate,
+  #[serde(default)]
+  kicks: Vec<Vec<Coordinate>>,
+}
+
+impl TryFrom<RawPiece> for PieceData {
+  type Error = String;
+
+  fn try_from(raw: RawPiece) -> Result<Self, Self::Error> {
+    let name = raw.name;
+    if name.is_empty() {
+      return Err(String::from("a piece has no name"));
+    }
+    if !(1..=GARBAGE_TILE_ID).contains(&raw.tile) {
+      return Err(format!("piece {name} has unknown tile {}", raw.tile));
+    }
+
+    let shape: Vec<_> = raw.rotations.iter().map(|rows| parse_shape(rows)).collect();
+    if shape.is_empty() || shape.iter().any(Vec::is_empty) {
+      return Err(format!("piece {name} has an empty rotation"));
+    }
+    if !raw.kicks.is_empty() && raw.kicks.len() != shape.len() {
+      return Err(format!("piece {name} needs kicks for each of its {} rotations", shape.len()));
+    }
+    if raw.kicks.iter().any(Vec::is_empty) {
+      return Err(format!("piece {name} has a rotation with no kicks"));
+    }
+
+    // the first rotation is shown in the preview
+    let fits_preview = shape[DEFAULT_ROTATION].iter().all(|cell| {
+      let cell = *cell + raw.preview_offset;
+      (0..PREVIEW_DIMENSIONS.x as i32).contains(&cell.x) && (0..PREVIEW_DIMENSIONS.y as i32).contains(&cell.y)
+    });
+    if !fits_preview {
+      return Err(format!("piece {name} doesn't fit in the preview"));
+    }
+
+    Ok(Self {
+      name,
+      tile_id: raw.tile,
+      shape,
+      preview_offset: raw.preview_offset,
+      spawn_offset: raw.spawn_offset,
+      kicks: raw.kicks,
+    })
+  }
+}
+
+/// A piece set as written in a piece set file
+#[derive(Deserialize)]
+struct RawPieceSet {
+  name: String,
+  pieces: Vec<RawPiece>,
+}
+
+impl TryFrom<RawPieceSet> for PieceSet {
+  type Error = String;
+
+  fn try_from(raw: RawPieceSet) -> Result<Self, Self::Error> {
+    let pieces = raw.pieces.into_iter()
+      .map(PieceData::try_from)
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|error| format!("{error} in piece set {}", raw.name))?;
+
+    if pieces.is_empty() {
+      return Err(format!("piece set {} has no pieces", raw.name));
+    }
+    let mut names = HashSet::new();
+    if let Some(piece) = pieces.iter().find(|piece| !names.insert(piece.name.as_str())) {
+      return Err(format!("piece {} is named twice in piece set {}", piece.name, raw.name));
+    }
+
+    Ok(Self { name: raw.name, pieces })
+  }
+}
+
+/// The pieces a game can be played with
+#[derive(Debug)]
+pub struct PieceSet {
+  pub name: String,
+  pieces: Vec<PieceData>,
+}
+
+impl PieceSet {
+  /// Build the set of the standard Tetriminos
+  pub fn standard() -> Self {
+    Self { name: String::from(STANDARD_PIECE_SET), pieces: standard_pieces() }
+  }
+
+  /// Read a piece set from `path`
+  pub fn load(path: &Path) -> Result<Self, String> {
+    Self::try_from(read_json::<RawPieceSet>(path)?)
+  }
+
+  /// Get the pieces of the set
+  pub fn pieces(&self) -> &[PieceData] {
+    &self.pieces
+  }
+
+  /// Get the piece named `name`
+  pub fn get(&self, name: &str) -> Option<&PieceData> {
+    self.pieces.iter().find(|piece| piece.name == name)
+  }
+
+  /// Check if the set is the standard Tetriminos
+  pub fn is_standard(&self) -> bool {
+    self.pieces == standard_piece_set().pieces
+  }
+}
+
+/// Play with `set` instead of the standard Tetriminos
+///
+/// The set can only be chosen before any pieces are played.
+pub fn use_piece_set(set: PieceSet) -> Result<(), String> {
+  PIECE_SET.set(set).map_err(|set| format!("failed to use piece set {}, a piece set is already in play", set.name))
+}
+
+/// Get the piece set in play
+pub fn piece_set() -> &'static PieceSet {
+  PIECE_SET.get_or_init(PieceSet::standard)
+}
+
+/// Get the set of the standard Tetriminos
+pub fn standard_piece_set() -> &'static PieceSet {
+  STANDARD_SET.get_or_init(PieceSet::standard)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{json, Value};
+
+  use super::*;
+
+  /// Build a valid domino as written in a piece set file
+  fn domino() -> Value {
+    json!({
+      "name": "D",
+      "tile": 1,
+      "rotations": [["##"], ["#", "#"]],
+      "kicks": [[{ "x": 0, "y": 0 }], [{ "x": 0, "y": 0 }]],
+    })
+  }
+
+  /// Build the piece set named `TEST` of `pieces`
+  fn build(pieces: Vec<Value>) -> Result<PieceSet, String> {
+    let raw: RawPieceSet = serde_json::from_value(json!({ "name": "TEST", "pieces": pieces })).expect("failed to parse piece set");
+    PieceSet::try_from(raw)
+  }
+
+  /// Build a piece set of a domino changed by `change`, returning the error
+  fn error_of(change: impl FnOnce(&mut Value)) -> String {
+    let mut piece = domino();
+    change(&mut piece);
+    build(vec![piece]).expect_err("failed to reject the piece set")
+  }
+
+  #[test]
+  fn builds_a_valid_set() {
+    let set = build(vec![domino()]).unwrap();
+    assert_eq!(set.name, "TEST");
+    assert_eq!(set.get("D").unwrap().shape, vec![parse_shape(&["##"]), parse_shape(&["#", "#"])]);
+  }
+
+  #[test]
+  fn loads_the_bundled_sets() {
+    for path in ["asset/pieces/tromino.json", "asset/pieces/pentomino.json"] {
+      assert!(PieceSet::load(Path::new(path)).is_ok(), "{path}");
+    }
+  }
+
+  #[test]
+  fn tells_the_standard_set_apart() {
+    assert!(PieceSet::standard().is_standard());
+    assert!(!PieceSet::load(Path::new("asset/pieces/tromino.json")).unwrap().is_standard());
+  }
+
+  #[test]
+  fn rejects_a_piece_with_no_name() {
+    assert_eq!(error_of(|piece| piece["name"] = json!("")), "a piece has no name in piece set TEST");
+  }
+
+  #[test]
+  fn rejects_an_unknown_tile() {
+    assert_eq!(error_of(|piece| piece["tile"] = json!(0)), "piece D has unknown tile 0 in piece set TEST");
+    assert_eq!(error_of(|piece| piece["tile"] = json!(GARBAGE_TILE_ID + 1)), "piece D has unknown tile 9 in piece set TEST");
+  }
+
+  #[test]
+  fn rejects_an_empty_rotation() {
+    assert_eq!(error_of(|piece| piece["rotations"] = json!([])), "piece D has an empty rotation in piece set TEST");
+    assert_eq!(error_of(|piece| piece["rotations"][1] = json!([" ", " "])), "piece D has an empty rotation in piece set TEST");
+  }
+
+  #[test]
+  fn rejects_kicks_missing_for_a_rotation() {
+    assert_eq!(
+      error_of(|piece| piece["kicks"] = json!([[{ "x": 0, "y": 0 }]])),
+      "piece D needs kicks for each of its 2 rotations in piece set TEST",
+    );
+  }
+
+  #[test]
+  fn rejects_a_rotation_with_no_kicks() {
+    assert_eq!(error_of(|piece| piece["kicks"][1] = json!([])), "piece D has a rotation with no kicks in piece set TEST");
+  }
+
+  #[test]
+  fn rejects_a_piece_too_big_for_the_preview() {
+    assert_eq!(error_of(|piece| piece["rotations"][0] = json!(["######"])), "piece D doesn't fit in the preview in piece set TEST");
+    assert_eq!(
+      error_of(|piece| piece["preview_offset"] = json!({ "x": -1, "y": 0 })),
+      "piece D doesn't fit in the preview in piece set TEST",
+    );
+  }
+
+  #[test]
+  fn rejects_a_set_with_no_pieces() {
+    assert_eq!(build(Vec::new()).unwrap_err(), "piece set TEST has no pieces");
+  }
+
+  #[test]
+  fn rejects_a_piece_named_twice() {
+    assert_eq!(build(vec![domino(), domino()]).unwrap_err(), "piece D is named twice in piece set TEST");
+  }
+}
@@ -0,0 +1,827 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ai::best_placement;
+use crate::ai::evaluate::Weights;
+use crate::ai::field::Field;
+use crate::ai::search::{find_placements, Pose};
+use crate::algorithm::{calculate_attack, calculate_score, calculate_speed_ms, determine_sfx};
+use crate::board::{Action, Board, BoardEvent, BoardInput, BoardSave, BoardSnapshot, BoardState};
+use crate::bot::Bot;
+use crate::constants::game::{BLOCK_OUT_TEXT, DRILL_TARGET_COLOR, FAULT_TEXT, FAULT_WARNING_DURATION, GARBAGE_OUT_TEXT, HARD_DROP_PARTICLES, HINT_COLOR, LEVEL_UP_PARTICLES, LOCK_BOUNCE_DEPTH, LOCK_BOUNCE_DIP, LOCK_BOUNCE_SETTLE, MAX_SHAKE, LINES_PER_LEVEL, LOCK_OUT_TEXT, MATRIX_DIMENSIONS, MAX_GARBAGE_PER_LOCK, MAX_TETRIS_LEVEL, OUT_OF_PIECES_TEXT, PREVIEW_DIMENSIONS, SCORE_ROLL_DURATION, SFX_VOLUME, SHAKE_DURATION, SHAKE_PER_LINE, SHAKE_PER_ROW, SPAWN_COOLDOWN, START_TETRIS_LEVEL, TILE_SIZE};
+use crate::constants::piece::{PieceData, ShapeType};
+use crate::controls::Controls;
+use crate::engine::asset::AssetManager;
+use crate::engine::asset::audio::Loop;
+use crate::engine::event::EventStore;
+use crate::engine::geometry::{Rec2, Vec2};
+use crate::engine::render::particle::ParticleSystem;
+use crate::engine::render::shake::Shake;
+use crate::engine::render::Renderer;
+use crate::engine::render::text::Text;
+use crate::engine::tile::tile::TileId;
+use crate::engine::tile::tilemap::Tilemap;
+use crate::engine::tile::tileset::Tileset;
+use crate::engine::time::{ConsumeAction, Timer};
+use crate::engine::tween::{Easing, Tween};
+use crate::engine::utility::random::random;
+use crate::engine::utility::types::{Coordinate, Size, Size2};
+use crate::finesse::Finesse;
+use crate::fumen::{self, Page};
+use crate::garbage::GarbageQueue;
+use crate::layout::{Anchor, layout, Readout};
+use crate::line_clear::{ClearAnimation, ClearEffect, ClearSave};
+use crate::puzzle::{Puzzle, PuzzleRun};
+use crate::piece::{Piece, write_piece};
+use crate::tbp::external::ExternalBot;
+
+/**
+ * A board, its HUD, and the rules for scoring and advancing levels
+ */
+
+/// Defines the outcome of a player update
+pub enum PlayerEvent {
+  /// Lines of garbage to send to an opponent
+  Attack { lines: u32 },
+  /// The player can no longer place pieces
+  ToppedOut(TopOut),
+  /// The player cleared the final level
+  Won,
+  Nothing,
+}
+
+/// Why a player can no longer place pieces
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TopOut {
+  /// A piece spawned overlapping the stack
+  BlockOut,
+  /// A piece locked wholly within the hidden buffer rows
+  LockOut,
+  /// Garbage pushed the stack above the buffer rows
+  Garbage,
+  /// The pieces to play ran out
+  OutOfPieces,
+}
+
+impl TopOut {
+  /// Describe the reason for display
+  pub fn describe(&self) -> &'static str {
+    match self {
+      TopOut::BlockOut => BLOCK_OUT_TEXT,
+      TopOut::LockOut => LOCK_OUT_TEXT,
+      TopOut::Garbage => GARBAGE_OUT_TEXT,
+      TopOut::OutOfPieces => OUT_OF_PIECES_TEXT,
+    }
+  }
+}
+
+/// The board and statistics of a player when a piece spawned
+#[derive(Clone, Debug)]
+struct Snapshot {
+  board: BoardSnapshot,
+  level: u32,
+  score: u32,
+  lines: u32,
+}
+
+/// Everything needed to resume a player's game where it was left
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerSave {
+  board: BoardSave,
+  level: u32,
+  score: u32,
+  lines: u32,
+  faults: u32,
+  /// lines being cleared, if they were
+  clearing: Option<ClearSave>,
+  /// time left on the spawn cooldown, if it was running
+  spawn_cooldown: Option<Duration>,
+}
+
+impl PlayerSave {
+  /// Get the visible columns and rows of the saved board
+  pub fn dimensions(&self) -> Size2 {
+    self.board.dimensions
+  }
+
+  /// Get the hidden rows above the visible rows of the saved board
+  pub fn buffer_rows(&self) -> Size {
+    self.board.buffer_rows
+  }
+}
+
+/// What decides the input for a board
+pub enum Controller {
+  Keyboard(Controls),
+  Bot(Bot),
+  /// A bot process speaking the Tetris Bot Protocol
+  External(ExternalBot),
+}
+
+/// A player and their board
+pub struct Player {
+  pub board: Board,
+  controller: Controller,
+  /// offset of the board and HUD from where the layout places them
+  offset: Vec2<i32>,
+
+  preview: Tilemap,
+  next_text: Text,
+  pub state_text: Text,
+  /// why the player topped out
+  top_out_text: Text,
+
+  level: u32,
+  score: u32,
+  lines: u32,
+  score_text: Text,
+  /// score shown, rolling up to the score
+  score_roll: Tween<u32>,
+  lines_text: Text,
+  level_text: Text,
+
+  spawn_cooldown: Timer,
+  /// full lines being removed before the lines above drop
+  clear_animation: Option<ClearAnimation>,
+  /// debris of cleared lines, hard drop trails, and level up showers
+  particles: ParticleSystem,
+  /// pixels the board dips when a piece locks
+  lock_bounce: Tween<f32>,
+  /// jolts the board on hard drops and multi-line clears
+  shake: Shake,
+  screen_shake: bool,
+
+  pub garbage: GarbageQueue,
+
+  /// show where the AI would place the active piece
+  show_hint: bool,
+  hint: Option<Pose>,
+
+  finesse: Finesse,
+  finesse_text: Text,
+  fault_text: Text,
+  fault_timer: Timer,
+  /// in drill mode every piece must reach a target with the fewest inputs
+  drill: bool,
+  drill_target: Option<Pose>,
+
+  puzzle: Option<PuzzleRun>,
+  objective_text: Text,
+
+  /// in practice placements can be undone
+  practice: bool,
+  /// snapshots from when each placed piece spawned
+  history: Vec<Snapshot>,
+  /// snapshot from when the active piece spawned
+  spawn_snapshot: Option<Snapshot>,
+}
+
+impl Player {
+  /// Instantiate a new player with a board of `dimensions` visible columns and rows below `buffer_rows` hidden rows, with
+  /// the board and HUD moved by `offset`
+  pub fn new(tileset: Rc<Tileset>, controller: Controller, offset: Vec2<i32>, dimensions: Size2, buffer_rows: Size) -> Self {
+    let layout = layout();
+
+    // create board
+    let mut board = Board::new(Rc::clone(&tileset), layout.board + offset, dimensions, buffer_rows);
+
+    // create preview
+    let mut preview_board = Tilemap::new(Rc::clone(&tileset), layout.preview.position(dimensions) + offset, PREVIEW_DIMENSIONS);
+    let label = |readout, content: &str| hud_text(readout, String::from(content), offset, dimensions);
+
+    // write preview
+    let BoardState { preview, .. } = board.next_piece();
+    write_preview(&mut preview_board, preview);
+
+    let mut player = Self {
+      board,
+      controller,
+      offset,
+      preview: preview_board,
+
+      spawn_cooldown: Timer::new(SPAWN_COOLDOWN, false),
+      clear_animation: None,
+      particles: ParticleSystem::new(tileset),
+      lock_bounce: Tween::at(0.0),
+      shake: Shake::new(),
+      screen_shake: true,
+      garbage: GarbageQueue::new(),
+      show_hint: false,
+      hint: None,
+
+      finesse: Finesse::new(),
+      finesse_text: label(Readout::Finesse, "faults 0000000"),
+      fault_text: label(Readout::Fault, ""),
+      fault_timer: Timer::new(FAULT_WARNING_DURATION, false),
+      drill: false,
+      drill_target: None,
+
+      puzzle: None,
+      objective_text: label(Readout::Objective, ""),
+
+      practice: false,
+      history: Vec::new(),
+      spawn_snapshot: None,
+
+      level: START_TETRIS_LEVEL,
+      score: 0,
+      lines: 0,
+      score_text: label(Readout::Score, "score 0000000"),
+      score_roll: Tween::at(0),
+      lines_text: label(Readout::Lines, "lines 0000000"),
+      level_text: label(Readout::Level, &format!("level {:0>7}", START_TETRIS_LEVEL)),
+      next_text: label(Readout::Next, "next"),
+      state_text: label(Readout::State, ""),
+      top_out_text: label(Readout::TopOut, ""),
+    };
+    player.begin_piece();
+    player
+  }
+
+  /// Replace the board and pieces with those of `puzzle`, and play for its objective
+  pub fn start_puzzle(&mut self, puzzle: &Puzzle) -> Result<(), String> {
+    self.start_setup(&puzzle.cells()?, &puzzle.shape_types()?, false);
+    self.objective_text.set_content(puzzle.objective.describe());
+    self.puzzle = Some(PuzzleRun::new(puzzle.objective));
+    Ok(())
+  }
+
+  /// Replace the board with the first of `pages`, and play the pieces of every page before random pieces
+  pub fn start_fumen(&mut self, pages: &[Page]) {
+    let pieces: Vec<ShapeType> = pages.iter().filter_map(|page| page.piece.map(|(shape_type, _)| shape_type)).collect();
+    let cells = pages.first().map_or(Vec::new(), |page| page.cells.clone());
+    self.start_setup(&cells, &pieces, true);
+  }
+
+  /// Replace the board with `cells` and play `pieces` next, then random pieces if `endless`
+  pub fn start_setup(&mut self, cells: &[(Coordinate, TileId)], pieces: &[ShapeType], endless: bool) {
+    self.board.set_sequence(pieces, endless);
+    self.board.clear();
+    for (coordinate, tile_id) in cells {
+      self.board.fill(coordinate, *tile_id);
+    }
+
+    let BoardState { preview, .. } = self.board.next_piece();
+    write_preview(&mut self.preview, preview);
+    self.begin_piece();
+  }
+
+  /// Get the board and active piece as a fumen
+  ///
+  /// returns an error unless the board is a standard board, the only board a fumen field describes
+  pub fn fumen(&self) -> Result<String, String> {
+    let size = self.board.matrix_dimensions();
+    if size != MATRIX_DIMENSIONS {
+      return Err(format!(
+        "fumens describe {}x{} boards, not {}x{}",
+        MATRIX_DIMENSIONS.x, MATRIX_DIMENSIONS.y, size.x, size.y
+      ));
+    }
+
+    let cells = self.board.cells();
+    let piece = self.board.piece().map(|piece| (piece.shape_type, Pose::from(piece)));
+    Ok(fumen::encode(&[Page { cells, piece, comment: String::new(), lock: true }]))
+  }
+
+  /// Keep a snapshot as each piece spawns so placements can be undone
+  pub fn start_practice(&mut self) {
+    self.practice = true;
+    self.spawn_snapshot = Some(self.snapshot());
+  }
+
+  /// Return to when the last placed piece spawned
+  ///
+  /// returns `false` if there is no placement to undo
+  pub fn undo(&mut self) -> bool {
+    let Some(snapshot) = self.history.pop() else {
+      return false;
+    };
+    self.restore(&snapshot);
+    true
+  }
+
+  /// Return to when practice started, to play the same pieces again
+  ///
+  /// returns `false` if not practicing
+  pub fn retry(&mut self) -> bool {
+    let Some(snapshot) = self.history.first().or(self.spawn_snapshot.as_ref()).cloned() else {
+      return false;
+    };
+    self.history.clear();
+    self.restore(&snapshot);
+    true
+  }
+
+  fn snapshot(&self) -> Snapshot {
+    Snapshot {
+      board: self.board.snapshot(),
+      level: self.level,
+      score: self.score,
+      lines: self.lines,
+    }
+  }
+
+  fn restore(&mut self, snapshot: &Snapshot) {
+    self.board.restore(&snapshot.board);
+    write_preview(&mut self.preview, self.board.preview());
+
+    // forget the placement being resolved
+    self.clear_animation = None;
+    self.particles.clear();
+    self.shake.stop();
+    self.top_out_text.clear_content();
+    self.spawn_cooldown.stop();
+
+    self.level = snapshot.level;
+    self.score = snapshot.score;
+    self.score_roll = Tween::at(self.score);
+    self.lines = snapshot.lines;
+    self.level_text.set_content(format!("LEVEL {:0>7}", self.level));
+    self.score_text.set_content(format!("SCORE {:0>7}", self.score));
+    self.lines_text.set_content(format!("LINES {:0>7}", self.lines));
+    self.board.set_speed_ms(calculate_speed_ms(self.level).expect("failed to calculate speed"));
+
+    self.begin_piece();
+  }
+
+  /// Save the board, statistics, and cooldowns to resume later
+  pub fn save(&self) -> PlayerSave {
+    PlayerSave {
+      board: self.board.save(),
+      level: self.level,
+      score: self.score,
+      lines: self.lines,
+      faults: self.finesse.faults,
+      clearing: self.clear_animation.as_ref().map(ClearAnimation::save),
+      spawn_cooldown: self.spawn_cooldown.remaining(),
+    }
+  }
+
+  /// Resume the game left in `save`
+  pub fn load(&mut self, save: &PlayerSave) -> Result<(), String> {
+    self.level = save.level;
+    self.score = save.score;
+    self.score_roll = Tween::at(self.score);
+    self.lines = save.lines;
+    self.level_text.set_content(format!("LEVEL {:0>7}", self.level));
+    self.score_text.set_content(format!("SCORE {:0>7}", self.score));
+    self.lines_text.set_content(format!("LINES {:0>7}", self.lines));
+    self.finesse_text.set_content(format!("FAULTS {:0>7}", save.faults));
+    self.board.set_speed_ms(calculate_speed_ms(self.level)?);
+
+    self.board.load(&save.board)?;
+    write_preview(&mut self.preview, self.board.preview());
+    let width = self.board.matrix_dimensions().x;
+    self.clear_animation = save.clearing.as_ref().map(|clearing| ClearAnimation::load(clearing, width));
+    self.spawn_cooldown.resume(save.spawn_cooldown);
+
+    // the resumed piece didn't spawn where its inputs would be counted from
+    self.finesse = Finesse::new();
+    self.finesse.faults = save.faults;
+    self.update_hint();
+    Ok(())
+  }
+
+  /// Present a target for every piece that must be reached with the fewest inputs
+  pub fn start_drill(&mut self) {
+    self.drill = true;
+    self.begin_piece();
+  }
+
+  // Rendering //
+
+  /// Render the board and HUD
+  pub fn render(&mut self, assets: &AssetManager, renderer: &mut Renderer, show_tiles: bool) {
+    self.board.render(renderer, show_tiles);
+    if show_tiles {
+      self.render_outlines(renderer);
+      if let Some(animation) = &self.clear_animation {
+        animation.render(&self.board, renderer);
+      }
+      self.particles.render(renderer);
+    }
+
+    render_label(&mut self.state_text, Readout::State, assets, renderer);
+    render_label(&mut self.top_out_text, Readout::TopOut, assets, renderer);
+
+    self.render_panels(renderer);
+    self.render_preview(assets, renderer);
+    self.render_statistics(assets, renderer);
+    self.render_garbage(renderer);
+    self.render_finesse(assets, renderer);
+    render_label(&mut self.objective_text, Readout::Objective, assets, renderer);
+  }
+
+  /// Outline the panels of the layout
+  fn render_panels(&self, renderer: &mut Renderer) {
+    let dimensions = self.board.dimensions();
+    for panel in &layout().panels {
+      renderer.draw_rect(panel.rect(dimensions).translate(self.offset), panel.color);
+    }
+  }
+
+  fn render_preview(&mut self, assets: &AssetManager, renderer: &mut Renderer) {
+    // draw preview
+    for tile in &self.preview {
+      if let Some(tile) = tile {
+        let position = Vec2::new(tile.position.x, tile.position.y);
+        renderer.draw_from_texture(&self.preview.tileset.texture, position, tile.src);
+      }
+    }
+
+    // draw text
+    render_label(&mut self.next_text, Readout::Next, assets, renderer);
+  }
+
+  fn render_statistics(&mut self, assets: &AssetManager, renderer: &mut Renderer) {
+    render_label(&mut self.level_text, Readout::Level, assets, renderer);
+    render_label(&mut self.score_text, Readout::Score, assets, renderer);
+    render_label(&mut self.lines_text, Readout::Lines, assets, renderer);
+  }
+
+  /// Outline the drill target and where the AI would place the active piece
+  fn render_outlines(&self, renderer: &mut Renderer) {
+    let Some(piece) = self.board.piece() else {
+      return;
+    };
+
+    for (pose, color) in [(self.hint, HINT_COLOR), (self.drill_target, DRILL_TARGET_COLOR)] {
+      if let Some(pose) = pose {
+        self.board.render_outline(renderer, &piece.shape_data[pose.rotation], &pose.position, color);
+      }
+    }
+  }
+
+  /// Draw the finesse faults of a player at the keyboard, and a warning after a fault
+  fn render_finesse(&mut self, assets: &AssetManager, renderer: &mut Renderer) {
+    if !matches!(self.controller, Controller::Keyboard(_)) {
+      return;
+    }
+
+    render_label(&mut self.finesse_text, Readout::Finesse, assets, renderer);
+    render_label(&mut self.fault_text, Readout::Fault, assets, renderer);
+  }
+
+  /// Draw a bar beside the board showing the garbage waiting to be added
+  fn render_garbage(&self, renderer: &mut Renderer) {
+    let rows = self.board.dimensions().y;
+    let pending = self.garbage.total().min(rows);
+    if pending == 0 {
+      return;
+    }
+
+    let meter = &layout().garbage_meter;
+    let top = meter.placement.position(self.board.dimensions());
+    let height = pending * TILE_SIZE;
+    let bottom = top.y + (rows * TILE_SIZE) as i32;
+    let origin = Vec2::new(top.x, bottom - height as i32) + self.offset;
+    renderer.fill_rect(Rec2::new(origin, Vec2::new(meter.width, height)), meter.color);
+  }
+
+  // Update //
+
+  /// Update the board with the players controller and resolve landed pieces
+  pub fn update(&mut self, events: &EventStore, assets: &AssetManager) -> PlayerEvent {
+    let mut player_event = PlayerEvent::Nothing;
+    self.particles.update();
+    self.update_tweens();
+
+    let board_event = match &mut self.controller {
+      Controller::Keyboard(controls) => {
+        if self.board.piece().is_some() {
+          self.finesse.record(controls, events);
+        }
+        self.board.update(&controls.poll(events))
+      }
+      Controller::Bot(bot) => {
+        let action = bot.poll(&self.board);
+        drive(&mut self.board, action)
+      }
+      Controller::External(bot) => {
+        let action = bot.poll(&self.board);
+        drive(&mut self.board, action)
+      }
+    };
+
+    match board_event {
+      BoardEvent::MoveLeft | BoardEvent::MoveRight => {
+        // play sound effect
+        assets.audio.play("move", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+      }
+      BoardEvent::Rotate => {
+        // play sound effect
+        assets.audio.play("rotate", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+      }
+      BoardEvent::Land | BoardEvent::HardDrop { .. } => {
+        // play sound effect
+        assets.audio.play("land", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+        if let BoardEvent::HardDrop { distance } = board_event {
+          self.emit_drop_trail(distance);
+          self.shake.start((distance as f32 * SHAKE_PER_ROW).min(MAX_SHAKE), SHAKE_DURATION);
+        }
+        self.lock_bounce = Tween::new(0.0, LOCK_BOUNCE_DEPTH, LOCK_BOUNCE_DIP, Easing::QuadOut)
+          .then(0.0, LOCK_BOUNCE_SETTLE, Easing::BounceOut);
+
+        // judge the inputs that placed the piece
+        let piece = self.board.piece().expect("failed to retrieve piece");
+        let (piece_data, landing) = (piece.shape_type.data(), Pose::from(piece));
+        self.judge_finesse(piece_data, landing);
+        let t_spin = self.board.is_t_spin();
+        let locked_out = self.board.is_locked_out();
+        if let Some(snapshot) = self.spawn_snapshot.take() {
+          self.history.push(snapshot);
+        }
+
+        // delete active piece
+        self.board.kill_piece();
+        self.hint = None;
+        if locked_out {
+          return self.top_out(TopOut::LockOut);
+        }
+
+        // drills start each piece on an empty board
+        if self.drill {
+          self.board.clear();
+          self.spawn_cooldown.start();
+          return player_event;
+        }
+
+        // check for full lines
+        let full_lines = self.board.get_full_lines();
+        let lines_cleared = full_lines.len() as u32;
+        if lines_cleared > 0 {
+          self.lines += lines_cleared;
+          self.lines_text.set_content(format!("LINES {:0>7}", self.lines));
+
+          if let Some(clear_line_sfx) = determine_sfx(lines_cleared) {
+            assets.audio.play(clear_line_sfx, SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+          }
+
+          if lines_cleared > 1 {
+            self.shake.start((lines_cleared as f32 * SHAKE_PER_LINE).min(MAX_SHAKE), SHAKE_DURATION);
+          }
+
+          // clear lines
+          let effect = ClearEffect::choose(lines_cleared, t_spin);
+          self.clear_animation = Some(ClearAnimation::new(effect, full_lines, self.board.matrix_dimensions().x));
+
+          // cancel incoming garbage then send the rest
+          let attack = calculate_attack(lines_cleared);
+          let attack = self.garbage.cancel(attack);
+          if attack > 0 {
+            player_event = PlayerEvent::Attack { lines: attack };
+          }
+        } else {
+          // no lines to clear, take incoming garbage
+          if !self.receive_garbage() {
+            return self.top_out(TopOut::Garbage);
+          }
+
+          // start the spawn cooldown
+          self.spawn_cooldown.start();
+        }
+
+        // check the puzzle objective
+        if let Some(puzzle) = &mut self.puzzle {
+          let mut field = self.board.field();
+          field.clear_lines();
+          let perfect_clear = field == Field::new(self.board.matrix_dimensions());
+          if puzzle.record(lines_cleared, t_spin, perfect_clear) {
+            return PlayerEvent::Won;
+          }
+        }
+      }
+      _ => {}
+    }
+
+    // check if the lines are cleared
+    let cleared = self.clear_animation.as_mut().is_some_and(|animation| animation.update(&mut self.board, &mut self.particles));
+    if let Some(animation) = self.clear_animation.take_if(|_| cleared) {
+      let lines_cleared = animation.lines().len() as u32;
+      // drop the lines above
+      for line in animation.lines() {
+        self.board.move_lines_down(*line).expect("failed to clear line");
+      }
+
+      // calculate score
+      let points = calculate_score(lines_cleared, self.level).expect("failed to calculate score");
+      self.score += points;
+      self.score_roll.retarget(self.score, SCORE_ROLL_DURATION, Easing::CubicOut);
+
+      // check level advance
+      if self.lines >= self.level * LINES_PER_LEVEL {
+        self.level += 1;
+        self.level_text.set_content(format!("LEVEL {:0>7}", self.level));
+
+        if self.level <= MAX_TETRIS_LEVEL {
+          let new_speed = calculate_speed_ms(self.level).expect("failed to calculate speed");
+          self.board.set_speed_ms(new_speed);
+          assets.audio.play("level", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+          self.emit_level_up();
+        } else {
+          return PlayerEvent::Won;
+        }
+      }
+
+      // drop sfx
+      assets.audio.play("shift", SFX_VOLUME, Loop::Once).expect("failed to play sound effect");
+
+      // start the spawn cooldown
+      self.spawn_cooldown.start();
+    }
+
+    // check if the spawn cooldown is done
+    if self.spawn_cooldown.consume(ConsumeAction::Disable) {
+      if !self.board.has_next_piece() {
+        return self.top_out(TopOut::OutOfPieces);
+      }
+      let BoardState { preview, space, .. } = self.board.next_piece();
+      write_preview(&mut self.preview, preview);
+      if !space {
+        return self.top_out(TopOut::BlockOut);
+      }
+      self.begin_piece();
+    }
+
+    // hide the fault warning
+    if self.fault_timer.consume(ConsumeAction::Disable) {
+      self.fault_text.clear_content();
+    }
+
+    player_event
+  }
+
+  /// Show why the player topped out
+  fn top_out(&mut self, reason: TopOut) -> PlayerEvent {
+    self.top_out_text.set_content(String::from(reason.describe()));
+    PlayerEvent::ToppedOut(reason)
+  }
+
+  /// Bounce and shake the board and roll the score toward their targets
+  fn update_tweens(&mut self) {
+    let bounce = Vec2::new(0, self.lock_bounce.update().round() as i32);
+    let shake = self.shake.offset();
+    self.board.set_offset(if self.screen_shake { bounce + shake } else { bounce });
+
+    if !self.score_roll.is_done() {
+      let score = self.score_roll.update();
+      self.score_text.set_content(format!("SCORE {:0>7}", score));
+    }
+  }
+
+  /// Trail each tile of the active piece up the `distance` rows it hard dropped
+  fn emit_drop_trail(&mut self, distance: u32) {
+    let Some(piece) = self.board.piece() else {
+      return;
+    };
+
+    for coord in &piece.shape_data[piece.rotation] {
+      let landing = Coordinate::new(piece.position.x + coord.x, piece.position.y + coord.y);
+      let top = Coordinate::new(landing.x, (landing.y - distance as i32).max(self.board.first_row()));
+      if top.y >= landing.y {
+        continue; // dropped no visible rows
+      }
+
+      // span the rows above the landing cell the tile fell through
+      let mut area = self.board.cell_area(&top);
+      area.size.y *= (landing.y - top.y) as u32;
+      self.particles.emit(&HARD_DROP_PARTICLES, area, piece.tile_data.id);
+    }
+  }
+
+  /// Shower the tiles of each piece down from the top of the board
+  fn emit_level_up(&mut self) {
+    let top = self.board.cell_area(&Coordinate::new(0, self.board.first_row()));
+    let area = Rec2::new(top.origin, Vec2::new(top.size.x * self.board.dimensions().x, top.size.y));
+    for shape_type in ShapeType::all() {
+      self.particles.emit(&LEVEL_UP_PARTICLES, area, shape_type.data().tile_id);
+    }
+  }
+
+  /// Prepare the hint, drill target, and finesse tracking for a new piece
+  fn begin_piece(&mut self) {
+    self.update_hint();
+    let Some(piece) = self.board.piece() else {
+      return;
+    };
+
+    let start = Pose::from(piece);
+    self.finesse.begin(start);
+    if self.practice {
+      self.spawn_snapshot = Some(self.snapshot());
+    }
+    if self.drill {
+      let mut placements = find_placements(&Field::new(self.board.matrix_dimensions()), piece.shape_type.data(), start);
+      self.drill_target = (!placements.is_empty()).then(|| placements.swap_remove(random(0, placements.len())).pose);
+    }
+  }
+
+  /// Judge the inputs that placed a piece of `piece_data` at `landing`, warning the player of a fault
+  fn judge_finesse(&mut self, piece_data: &PieceData, landing: Pose) {
+    if !matches!(self.controller, Controller::Keyboard(_)) {
+      return;
+    }
+
+    let size = self.board.matrix_dimensions();
+    let fault = match self.drill_target.take() {
+      Some(target) => self.finesse.judge_drill(size, piece_data, landing, target),
+      None => self.finesse.judge(size, piece_data, landing),
+    };
+    if fault {
+      self.finesse_text.set_content(format!("FAULTS {:0>7}", self.finesse.faults));
+      self.fault_text.set_content(String::from(FAULT_TEXT));
+      self.fault_timer.start();
+    }
+  }
+
+  /// Show or hide the hint, only players at the keyboard are given hints
+  pub fn set_hint(&mut self, show: bool) {
+    self.show_hint = show && matches!(self.controller, Controller::Keyboard(_));
+    self.update_hint();
+  }
+
+  /// Shake the board on hard drops and multi-line clears if `enabled`
+  pub fn set_screen_shake(&mut self, enabled: bool) {
+    self.screen_shake = enabled;
+  }
+
+  /// Draw the falling piece sliding between rows if `enabled`
+  pub fn set_smooth_fall(&mut self, enabled: bool) {
+    self.board.set_smooth_fall(enabled);
+  }
+
+  /// Find the best placement for the active piece if hints are shown
+  fn update_hint(&mut self) {
+    self.hint = None;
+    if !self.show_hint {
+      return;
+    }
+
+    if let Some(piece) = self.board.piece() {
+      let next = self.board.preview().map(|preview| preview.shape_type);
+      self.hint = best_placement(&self.board.field(), piece.shape_type, Pose::from(piece), next, self.board.first_row(), &Weights::default())
+        .map(|placement| placement.pose);
+    }
+  }
+
+  /// Add queued garbage to the board
+  ///
+  /// returns `false` if the garbage pushed tiles off the board
+  fn receive_garbage(&mut self) -> bool {
+    let mut space = true;
+    for lines in self.garbage.take(MAX_GARBAGE_PER_LOCK) {
+      let hole = random(0, self.board.dimensions().x as i32);
+      space &= self.board.add_garbage(lines, hole);
+    }
+    space
+  }
+}
+
+/// Get the pixels taken up by a player with a board of `dimensions` visible columns and rows, and its HUD
+pub fn player_pixels(dimensions: Size2) -> Size2 {
+  let size = layout().size;
+  let shift = Anchor::BottomRight.shift(dimensions);
+  let width = size.x as i32 + shift.x;
+  let height = size.y as i32 + shift.y.max(0);
+  Vec2::new(width as u32, height as u32)
+}
+
+/// Build the text showing `readout` where the layout places it beside a board of `dimensions`, moved by `offset`
+fn hud_text(readout: Readout, content: String, offset: Vec2<i32>, dimensions: Size2) -> Text {
+  match layout().label(readout) {
+    Some(label) => {
+      let color = label.color.unwrap_or(readout.color());
+      Text::new(content, color, label.placement.position(dimensions) + offset)
+    }
+    None => Text::new(content, readout.color(), offset), // never drawn
+  }
+}
+
+/// Draw `text` in the typeface the layout chooses for `readout`, unless the layout leaves it out
+fn render_label(text: &mut Text, readout: Readout, assets: &AssetManager, renderer: &mut Renderer) {
+  let Some(label) = layout().label(readout) else {
+    return;
+  };
+  let typeface = assets.typefaces
+    .use_store()
+    .get(&label.font)
+    .expect("failed to fetch typeface");
+  text.render(&typeface, &assets.textures, renderer);
+}
+
+/// Execute the `action` a bot chose, or let gravity act on `board` if it chose nothing
+fn drive(board: &mut Board, action: Option<Action>) -> BoardEvent {
+  match action {
+    Some(action) => board.execute(action),
+    None => board.update(&BoardInput::default()),
+  }
+}
+
+fn write_preview(preview: &mut Tilemap, piece: Option<&Piece>) {
+  preview.clear_tiles();
+  if let Some(piece) = piece {
+    write_piece(piece, preview);
+  }
+}
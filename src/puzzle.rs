@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::piece::ShapeType;
+use crate::constants::window::{APPLICATION, ORGANIZATION};
+use crate::engine::storage::{read_json, user_path, write_json};
+use crate::engine::tile::tile::TileId;
+use crate::engine::utility::types::Coordinate;
+use crate::setup::Setup;
+
+/**
+ * Preset boards with a fixed set of pieces and a goal to reach with them
+ */
+
+/// File in the user data directory listing the solved puzzles
+const PROGRESS_FILE: &str = "puzzles.json";
+
+/// What must be done to solve a puzzle
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Objective {
+  /// Clear `lines` lines
+  ClearLines { lines: u32 },
+  /// Clear two lines with a T that spun into place
+  TSpinDouble,
+  /// Empty the board within `pieces` pieces
+  PerfectClear { pieces: u32 },
+}
+
+impl Objective {
+  /// Describe the objective for display
+  pub fn describe(&self) -> String {
+    match self {
+      Objective::ClearLines { lines } => format!("CLEAR {lines} LINES"),
+      Objective::TSpinDouble => String::from("T-SPIN DOUBLE"),
+      Objective::PerfectClear { pieces } => format!("PERFECT CLEAR IN {pieces}"),
+    }
+  }
+}
+
+/// A puzzle as written in the puzzle file
+#[derive(Clone, Debug, Deserialize)]
+pub struct Puzzle {
+  pub name: String,
+  #[serde(flatten)]
+  pub setup: Setup,
+  pub objective: Objective,
+}
+
+impl Puzzle {
+  /// Get the pieces of the puzzle
+  pub fn shape_types(&self) -> Result<Vec<ShapeType>, String> {
+    self.setup.shape_types().map_err(|error| format!("{error} in puzzle {}", self.name))
+  }
+
+  /// Get the filled cells of the board and the tile to fill them with
+  pub fn cells(&self) -> Result<Vec<(Coordinate, TileId)>, String> {
+    self.setup.cells().map_err(|error| format!("{error} in puzzle {}", self.name))
+  }
+}
+
+/// Read every puzzle from the puzzle file at `path`
+pub fn load_puzzles(path: &Path) -> Result<Vec<Puzzle>, String> {
+  let puzzles: Vec<Puzzle> = read_json(path)?;
+  for puzzle in &puzzles {
+    // catch mistakes in the file before the puzzle is played
+    puzzle.shape_types()?;
+    puzzle.cells()?;
+  }
+  Ok(puzzles)
+}
+
+/// An attempt at a puzzle
+#[derive(Debug)]
+pub struct PuzzleRun {
+  pub objective: Objective,
+  lines: u32,
+  pieces: u32,
+}
+
+impl PuzzleRun {
+  /// Begin an attempt at `objective`
+  pub fn new(objective: Objective) -> Self {
+    Self { objective, lines: 0, pieces: 0 }
+  }
+
+  /// Record a piece that cleared `lines`, with a `t_spin` or a `perfect_clear`
+  ///
+  /// returns `true` if the objective has been met
+  pub fn record(&mut self, lines: u32, t_spin: bool, perfect_clear: bool) -> bool {
+    self.lines += lines;
+    self.pieces += 1;
+    match self.objective {
+      Objective::ClearLines { lines } => self.lines >= lines,
+      Objective::TSpinDouble => t_spin && lines == 2,
+      Objective::PerfectClear { pieces } => perfect_clear && self.pieces <= pieces,
+    }
+  }
+}
+
+/// The names of the solved puzzles, saved to the user data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+  solved: BTreeSet<String>,
+  #[serde(skip)]
+  path: Option<PathBuf>,
+}
+
+impl PuzzleProgress {
+  /// Load the solved puzzles, starting without any if they can't be read
+  pub fn load() -> Self {
+    let path = user_path(ORGANIZATION, APPLICATION, PROGRESS_FILE)
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+    let mut progress: Self = path.as_deref()
+      .filter(|path| path.exists())
+      .and_then(|path| read_json(path).map_err(|error| eprintln!("{error}")).ok())
+      .unwrap_or_default();
+    progress.path = path;
+    progress
+  }
+
+  /// Check if the puzzle named `name` has been solved
+  pub fn is_solved(&self, name: &str) -> bool {
+    self.solved.contains(name)
+  }
+
+  /// Mark the puzzle named `name` as solved and save
+  pub fn solve(&mut self, name: &str) {
+    if self.solved.insert(String::from(name)) {
+      if let Some(path) = &self.path {
+        write_json(path, self).map_err(|error| eprintln!("{error}")).ok();
+      }
+    }
+  }
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::window::{APPLICATION, ORGANIZATION};
+use crate::engine::storage::{read_json, user_path, write_json};
+use crate::player::PlayerSave;
+
+/**
+ * An unfinished game saved to resume on the next launch
+ */
+
+/// File in the user data directory the unfinished game is saved to
+const SAVE_FILE: &str = "save.json";
+
+/// The single player modes a game can be saved in
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedMode {
+  Marathon,
+  Practice,
+  Edited,
+}
+
+/// An unfinished game and the mode it was played in
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+  pub mode: SavedMode,
+  pub player: PlayerSave,
+}
+
+impl SavedGame {
+  /// Read the saved game, if there is one that can be read
+  pub fn load() -> Option<Self> {
+    let path = save_path()?;
+    if !path.exists() {
+      return None;
+    }
+    read_json(&path).map_err(|error| eprintln!("{error}")).ok()
+  }
+
+  /// Write the game over any saved before
+  pub fn save(&self) {
+    if let Some(path) = save_path() {
+      write_json(&path, self).map_err(|error| eprintln!("{error}")).ok();
+    }
+  }
+
+  /// Remove the saved game so it can't be continued
+  pub fn delete() {
+    let Some(path) = save_path().filter(|path| path.exists()) else {
+      return;
+    };
+    if let Err(error) = fs::remove_file(&path) {
+      eprintln!("failed to remove {}: {error}", path.display());
+    }
+  }
+}
+
+fn save_path() -> Option<PathBuf> {
+  user_path(ORGANIZATION, APPLICATION, SAVE_FILE).map_err(|error| eprintln!("{error}")).ok()
+}
@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::window::{APPLICATION, ORGANIZATION};
+use crate::engine::geometry::Vec2;
+use crate::engine::render::Scaling;
+use crate::engine::storage::{read_json, user_path, write_json};
+
+/**
+ * Preferences kept between sessions
+ */
+
+/// File in the user data directory the settings are saved to
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Preferences that apply to every game, saved to the user data directory
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+  /// shake the board on hard drops and multi-line clears, off for motion-sensitive players
+  pub screen_shake: bool,
+  /// draw the falling piece sliding between rows instead of jumping a row at a time
+  pub smooth_fall: bool,
+  /// how the game is scaled to fill the window
+  pub scaling: Scaling,
+  /// size of the window when last closed, unless it was fullscreen
+  pub window_size: Option<Vec2<u32>>,
+  /// position of the window when last closed, unless it was fullscreen
+  pub window_position: Option<Vec2<i32>>,
+  #[serde(skip)]
+  path: Option<PathBuf>,
+}
+
+impl Default for Settings {
+  fn default() -> Self {
+    Self {
+      screen_shake: true,
+      smooth_fall: false,
+      scaling: Scaling::default(),
+      window_size: None,
+      window_position: None,
+      path: None,
+    }
+  }
+}
+
+impl Settings {
+  /// Load the settings, starting with the defaults if they can't be read
+  pub fn load() -> Self {
+    let path = user_path(ORGANIZATION, APPLICATION, SETTINGS_FILE)
+      .map_err(|error| eprintln!("{error}"))
+      .ok();
+    let mut settings: Self = path.as_deref()
+      .filter(|path| path.exists())
+      .and_then(|path| read_json(path).map_err(|error| eprintln!("{error}")).ok())
+      .unwrap_or_default();
+    settings.path = path;
+    settings
+  }
+
+  /// Write the settings over any saved before
+  pub fn save(&self) {
+    if let Some(path) = &self.path {
+      write_json(path, self).map_err(|error| eprintln!("{error}")).ok();
+    }
+  }
+}
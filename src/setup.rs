@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::game::{GARBAGE_TILE_ID, MATRIX_DIMENSIONS};
+use crate::constants::piece::ShapeType;
+use crate::engine::tile::tile::TileId;
+use crate::engine::utility::types::Coordinate;
+
+/**
+ * A board and the pieces to play on it, written as text
+ */
+
+/// Characters of a board row that leave a cell empty, the first is written
+const EMPTY_CELLS: [char; 2] = ['.', ' '];
+/// Characters of a board row that fill a cell with garbage, the first is written
+const GARBAGE_CELLS: [char; 2] = ['G', '#'];
+
+/// A board and the pieces to play on it
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Setup {
+  /// rows of the board from the top down, resting on the floor
+  ///
+  /// Piece letters fill a cell with that piece, `G` or `#` with garbage, and a space or `.` leaves it empty.
+  pub board: Vec<String>,
+  /// letters of the pieces to play, in order
+  pub pieces: Vec<String>,
+}
+
+impl Setup {
+  /// Write the tiles `cells` of a board, and `pieces`, as a setup
+  pub fn new(cells: &[(Coordinate, TileId)], pieces: &[ShapeType]) -> Self {
+    let top = cells.iter().map(|(coordinate, _)| coordinate.y).min().unwrap_or(MATRIX_DIMENSIONS.y as i32);
+    let mut board: Vec<Vec<char>> = (top..MATRIX_DIMENSIONS.y as i32)
+      .map(|_| vec![EMPTY_CELLS[0]; MATRIX_DIMENSIONS.x as usize])
+      .collect();
+    for (coordinate, tile_id) in cells {
+      let cell = ShapeType::STANDARD.iter()
+        .find(|shape_type| shape_type.data().tile_id == *tile_id)
+        .and_then(|shape_type| shape_type.name().chars().next())
+        .unwrap_or(GARBAGE_CELLS[0]);
+      board[(coordinate.y - top) as usize][coordinate.x as usize] = cell;
+    }
+
+    Self {
+      board: board.into_iter().map(String::from_iter).collect(),
+      pieces: pieces.iter().map(|shape_type| String::from(shape_type.name())).collect(),
+    }
+  }
+
+  /// Get the pieces to play
+  pub fn shape_types(&self) -> Result<Vec<ShapeType>, String> {
+    self.pieces.iter()
+      .map(|piece| ShapeType::from_name(piece).ok_or(format!("unknown piece {piece}")))
+      .collect()
+  }
+
+  /// Get the filled cells of the board and the tile to fill them with
+  pub fn cells(&self) -> Result<Vec<(Coordinate, TileId)>, String> {
+    let (width, height) = MATRIX_DIMENSIONS.destructure();
+    if self.board.len() > height as usize {
+      return Err(format!("board has {} rows, more than the {height} of a board", self.board.len()));
+    }
+
+    let top = height as i32 - self.board.len() as i32;
+    let mut cells = Vec::new();
+    for (y, row) in self.board.iter().enumerate() {
+      if row.chars().count() > width as usize {
+        return Err(format!("row {y} of the board is wider than the {width} columns of a board"));
+      }
+      for (x, cell) in row.chars().enumerate().filter(|(_, cell)| !EMPTY_CELLS.contains(cell)) {
+        let tile_id = match cell {
+          cell if GARBAGE_CELLS.contains(&cell) => GARBAGE_TILE_ID,
+          _ => ShapeType::from_name(&cell.to_string())
+            .ok_or(format!("unknown cell {cell}"))?
+            .data()
+            .tile_id,
+        };
+        cells.push((Coordinate::new(x as i32, top + y as i32), tile_id));
+      }
+    }
+    Ok(cells)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Build a setup of `board` rows with no pieces
+  fn setup(board: &[&str]) -> Setup {
+    Setup { board: board.iter().map(|row| String::from(*row)).collect(), pieces: Vec::new() }
+  }
+
+  #[test]
+  fn reads_cells_resting_on_the_floor() {
+    let bottom = MATRIX_DIMENSIONS.y as i32 - 1;
+    let cells = setup(&["T..", "G#I"]).cells().unwrap();
+    assert_eq!(cells, vec![
+      (Coordinate::new(0, bottom - 1), ShapeType::T.data().tile_id),
+      (Coordinate::new(0, bottom), GARBAGE_TILE_ID),
+      (Coordinate::new(1, bottom), GARBAGE_TILE_ID),
+      (Coordinate::new(2, bottom), ShapeType::I.data().tile_id),
+    ]);
+  }
+
+  #[test]
+  fn round_trips_cells_and_pieces() {
+    let cells = setup(&["..........", "GGGG.GGGGG", "IIIITTTOO."]).cells().unwrap();
+    let written = Setup::new(&cells, &[ShapeType::S, ShapeType::Z]);
+    assert_eq!(written.cells().unwrap(), cells);
+    assert_eq!(written.shape_types().unwrap(), vec![ShapeType::S, ShapeType::Z]);
+  }
+
+  #[test]
+  fn rejects_unknown_cells() {
+    assert!(setup(&["X"]).cells().is_err());
+  }
+
+  #[test]
+  fn rejects_a_row_wider_than_the_board() {
+    let row = "G".repeat(MATRIX_DIMENSIONS.x as usize + 1);
+    assert!(setup(&[&row]).cells().is_err());
+    assert!(setup(&[&"G".repeat(MATRIX_DIMENSIONS.x as usize)]).cells().is_ok());
+  }
+
+  #[test]
+  fn rejects_more_rows_than_the_board() {
+    let rows = vec!["G"; MATRIX_DIMENSIONS.y as usize + 1];
+    assert!(setup(&rows).cells().is_err());
+    assert!(setup(&rows[1..]).cells().is_ok());
+  }
+}
@@ -0,0 +1,162 @@
+use serde::Serialize;
+
+use crate::ai::best_placement;
+use crate::ai::evaluate::Weights;
+use crate::ai::field::Field;
+use crate::ai::search::{find_placements, Pose};
+use crate::algorithm::{calculate_score, check_shape_collision, transform_shape};
+use crate::bot::Difficulty;
+use crate::constants::game::{BOARD_DIMENSIONS, FIRST_ROW, LINES_PER_LEVEL, MATRIX_DIMENSIONS, MAX_TETRIS_LEVEL, SIMULATION_GARBAGE_INTERVAL, START_TETRIS_LEVEL};
+use crate::constants::piece::{DEFAULT_ROTATION, ShapeType};
+use crate::engine::utility::random::SeededRandom;
+use crate::piece::spawn_position;
+
+/**
+ * Play games with the AI and no window, as fast as it can place pieces
+ */
+
+/// The rules a simulated game is played by
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+  /// Clear lines until the final level
+  Marathon,
+  /// Clear lines until topping out
+  Endless,
+  /// Clear lines while garbage rises from the floor
+  Garbage,
+}
+
+impl Preset {
+  pub const ALL: [Preset; 3] = [Preset::Marathon, Preset::Endless, Preset::Garbage];
+
+  /// Get the name of the preset
+  pub fn name(&self) -> &'static str {
+    match self {
+      Preset::Marathon => "marathon",
+      Preset::Endless => "endless",
+      Preset::Garbage => "garbage",
+    }
+  }
+
+  /// Get the preset named `name`, ignoring case
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|preset| preset.name().eq_ignore_ascii_case(name))
+  }
+}
+
+/// How a batch of games is played
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Simulation {
+  pub games: u32,
+  /// seed of the first game, each game after uses the next seed
+  pub seed: u64,
+  pub preset: Preset,
+  pub difficulty: Difficulty,
+  pub weights: Weights,
+  /// the most pieces placed before a game is stopped
+  pub max_pieces: u32,
+}
+
+/// The outcome of one simulated game
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct GameResult {
+  pub lines: u32,
+  pub score: u32,
+  pub pieces: u32,
+  pub topped_out: bool,
+}
+
+/// Statistics of a batch of simulated games
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Summary {
+  pub simulation: Simulation,
+  pub average_lines: f32,
+  pub average_score: f32,
+  pub average_pieces: f32,
+  /// fraction of games that ended by topping out
+  pub top_out_rate: f32,
+}
+
+impl Simulation {
+  /// Play every game and summarize the results
+  pub fn run(&self) -> Summary {
+    let results: Vec<GameResult> = (0..self.games as u64)
+      .map(|game| self.play(self.seed.wrapping_add(game)))
+      .collect();
+
+    let games = results.len().max(1) as f32;
+    let average = |stat: fn(&GameResult) -> u32| results.iter().map(stat).sum::<u32>() as f32 / games;
+    Summary {
+      simulation: *self,
+      average_lines: average(|result| result.lines),
+      average_score: average(|result| result.score),
+      average_pieces: average(|result| result.pieces),
+      top_out_rate: results.iter().filter(|result| result.topped_out).count() as f32 / games,
+    }
+  }
+
+  /// Play one game with pieces and mistakes drawn from `seed`
+  pub fn play(&self, seed: u64) -> GameResult {
+    let mut random = SeededRandom::new(seed);
+    let mut field = Field::new(MATRIX_DIMENSIONS);
+    let mut level = START_TETRIS_LEVEL;
+    let mut result = GameResult::default();
+
+    let shape_types = ShapeType::all();
+    let mut current = shape_types[random.range(0, shape_types.len())];
+    let mut next = shape_types[random.range(0, shape_types.len())];
+    while result.pieces < self.max_pieces {
+      let piece = current.data();
+      let start = Pose::new(DEFAULT_ROTATION, spawn_position(&current, MATRIX_DIMENSIONS.x, FIRST_ROW));
+      if check_shape_collision(&transform_shape(&piece.shape[start.rotation], &start.position), &field) {
+        result.topped_out = true; // no space to spawn
+        break;
+      }
+
+      let placement = if random.range(0.0, 1.0) < self.difficulty.mistake_rate() {
+        let mut placements = find_placements(&field, piece, start);
+        (!placements.is_empty()).then(|| placements.swap_remove(random.range(0, placements.len())))
+      } else {
+        let next = self.difficulty.lookahead().then_some(next);
+        best_placement(&field, current, start, next, FIRST_ROW, &self.weights)
+      };
+      let Some(placement) = placement else {
+        result.topped_out = true;
+        break;
+      };
+
+      let shape = &piece.shape[placement.pose.rotation];
+      if shape.iter().all(|coord| placement.pose.position.y + coord.y < FIRST_ROW) {
+        result.topped_out = true; // locked out
+        break;
+      }
+
+      let lines = field.lock(shape, &placement.pose.position);
+      result.pieces += 1;
+      result.lines += lines;
+      result.score += calculate_score(lines, level.min(MAX_TETRIS_LEVEL)).expect("failed to calculate score");
+
+      // check level advance
+      if result.lines >= level * LINES_PER_LEVEL {
+        level += 1;
+        if level > MAX_TETRIS_LEVEL && self.preset == Preset::Marathon {
+          break; // won
+        }
+      }
+
+      if self.preset == Preset::Garbage && result.pieces % SIMULATION_GARBAGE_INTERVAL == 0 {
+        let hole = random.range(0, BOARD_DIMENSIONS.x as i32);
+        if !field.add_garbage(1, hole) {
+          result.topped_out = true;
+          break;
+        }
+      }
+
+      current = next;
+      next = shape_types[random.range(0, shape_types.len())];
+    }
+
+    result
+  }
+}
@@ -0,0 +1,221 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+use crate::ai::field::Field;
+use crate::ai::search::{find_path, Pose, step};
+use crate::board::{Action, Board};
+use crate::bot::{drop_time, Pilot};
+use crate::tbp::{field_to_board, location_to_pose, piece_name, pose_to_location};
+use crate::tbp::message::{BotMessage, FrontendMessage, Move, Spin, Start};
+
+/**
+ * Let a bot running in another process play a board
+ */
+
+/// How far the conversation with the bot has come
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Status {
+  /// waiting for the bot to introduce itself
+  Launching,
+  /// waiting for the bot to accept the rules
+  Configuring,
+  /// waiting for a piece to ask about
+  Ready,
+  /// waiting for a suggestion
+  Thinking,
+  /// the bot has exited or broken the protocol, pieces are dropped in place
+  Disconnected,
+}
+
+/// Produces actions for a board from the suggestions of a bot process
+pub struct ExternalBot {
+  process: Child,
+  input: ChildStdin,
+  messages: Receiver<BotMessage>,
+  status: Status,
+  pilot: Pilot,
+  /// the most pieces the bot will place each second
+  pieces_per_second: f32,
+  /// the field the bot believes the board has, if it is still in sync
+  expected: Option<Field>,
+  /// has the bot been given a position
+  started: bool,
+}
+
+impl ExternalBot {
+  /// Launch the bot `command`, a program followed by its arguments
+  pub fn launch(command: &str, pieces_per_second: f32) -> Result<Self, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("bot command is empty")?;
+    let mut process = Command::new(program)
+      .args(parts)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .map_err(|error| format!("failed to launch bot: {error}"))?;
+    let input = process.stdin.take().ok_or("failed to open bot input")?;
+    let output = process.stdout.take().ok_or("failed to open bot output")?;
+
+    // read on another thread so a slow bot doesn't stall the game
+    let (sender, messages) = channel();
+    thread::spawn(move || {
+      for line in BufReader::new(output).lines() {
+        let Ok(line) = line else { break; };
+        // messages we don't understand are ignored
+        if let Ok(message) = serde_json::from_str::<BotMessage>(&line) {
+          if sender.send(message).is_err() {
+            break;
+          }
+        }
+      }
+    });
+
+    Ok(Self {
+      process,
+      input,
+      messages,
+      status: Status::Launching,
+      pilot: Pilot::new(),
+      pieces_per_second,
+      expected: None,
+      started: false,
+    })
+  }
+
+  /// Decide the action for `board` this frame, if any
+  pub fn poll(&mut self, board: &Board) -> Option<Action> {
+    self.receive(board);
+
+    let Some(piece) = board.piece() else {
+      self.pilot.reset(); // the piece has landed, ask about the next one
+      return None;
+    };
+
+    let pose = Pose::from(piece);
+    if self.pilot.target().is_none() {
+      match self.status {
+        Status::Ready => self.request(board),
+        Status::Disconnected => self.pilot.follow(None, pose, drop_time(self.pieces_per_second)),
+        _ => {}
+      }
+    } else if !self.pilot.on_course(pose) && !self.pilot.reroute(board) {
+      // gravity moved the piece somewhere the move can't be reached from
+      self.pilot.follow(None, pose, drop_time(self.pieces_per_second));
+      self.expected = None;
+    }
+
+    self.pilot.next_action()
+  }
+
+  /// Handle the messages the bot has sent since the last poll
+  fn receive(&mut self, board: &Board) {
+    loop {
+      let message = match self.messages.try_recv() {
+        Ok(message) => message,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => {
+          self.status = Status::Disconnected;
+          return;
+        }
+      };
+
+      match (self.status, message) {
+        (Status::Launching, BotMessage::Info { .. }) => {
+          self.status = Status::Configuring;
+          self.send(&FrontendMessage::Rules);
+        }
+        (Status::Configuring, BotMessage::Ready) => self.status = Status::Ready,
+        (Status::Thinking, BotMessage::Suggestion { moves }) => self.play(board, &moves),
+        (_, BotMessage::Error { reason }) => {
+          eprintln!("bot refused to play: {reason}");
+          self.status = Status::Disconnected;
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Tell the bot about the active piece of `board` and ask for a suggestion
+  fn request(&mut self, board: &Board) {
+    let (Some(piece), Some(preview)) = (board.piece(), board.preview()) else {
+      return;
+    };
+
+    let field = board.field();
+    if self.expected.as_ref() == Some(&field) {
+      // the bot already knows the active piece, it was the preview
+      self.send(&FrontendMessage::NewPiece { piece: piece_name(preview.shape_type) });
+    } else {
+      // garbage or a missed move changed the board, start over
+      if self.started {
+        self.send(&FrontendMessage::Stop);
+      }
+      self.send(&FrontendMessage::Start(Start {
+        hold: None,
+        queue: vec![piece_name(piece.shape_type), piece_name(preview.shape_type)],
+        combo: 0,
+        back_to_back: false,
+        board: field_to_board(&field),
+      }));
+      self.started = true;
+    }
+
+    self.send(&FrontendMessage::Suggest);
+    if self.status == Status::Ready {
+      self.status = Status::Thinking;
+    }
+  }
+
+  /// Follow the first of `moves` the active piece of `board` can reach, or drop it in place
+  fn play(&mut self, board: &Board, moves: &[Move]) {
+    self.status = Status::Ready;
+    let Some(piece) = board.piece() else {
+      self.expected = None; // the piece landed before the bot answered
+      return;
+    };
+
+    let pose = Pose::from(piece);
+    let height = board.matrix_dimensions().y;
+    let mut field = board.field();
+    let placement = moves.iter()
+      .filter_map(|suggestion| location_to_pose(&suggestion.location, height))
+      .filter(|(shape_type, _)| *shape_type == piece.shape_type)
+      .find_map(|(_, target)| find_path(&field, piece.shape_type.data(), pose, target));
+
+    let target = match &placement {
+      Some(placement) => placement.pose,
+      None => step(&field, piece.shape_type.data(), &pose, Action::HardDrop).unwrap_or(pose),
+    };
+    let Some(location) = pose_to_location(piece.shape_type, &target, height) else {
+      eprintln!("failed to tell the bot where piece {} went, dropping pieces in place", piece.shape_type.name());
+      self.status = Status::Disconnected;
+      self.expected = None;
+      self.pilot.follow(None, pose, drop_time(self.pieces_per_second));
+      return;
+    };
+    self.send(&FrontendMessage::Play { placement: Move { location, spin: Spin::None } });
+
+    field.lock(&piece.shape_data[target.rotation], &target.position);
+    self.expected = Some(field);
+    self.pilot.follow(placement, pose, drop_time(self.pieces_per_second));
+  }
+
+  /// Write `message` to the bot as a line of JSON
+  fn send(&mut self, message: &FrontendMessage) {
+    let json = serde_json::to_string(message).expect("failed to serialize message");
+    if writeln!(self.input, "{json}").and_then(|_| self.input.flush()).is_err() {
+      self.status = Status::Disconnected;
+    }
+  }
+}
+
+impl Drop for ExternalBot {
+  /// Ask the bot to quit, and make sure it does
+  fn drop(&mut self) {
+    self.send(&FrontendMessage::Quit);
+    self.process.kill().ok();
+    self.process.wait().ok();
+  }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * Messages exchanged with a bot, one JSON object per line
+ */
+
+/// A cell of a board, the name of what fills it or `None` if empty
+pub type Cell = Option<String>;
+
+/// Messages sent from the frontend (the game) to a bot
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FrontendMessage {
+  /// The rules of the game, answered with `ready` or `error`
+  Rules,
+  /// Begin calculating from a position
+  Start(Start),
+  /// Ask for the moves the bot would make with the current piece
+  Suggest,
+  /// Tell the bot a move was made, advancing its queue
+  Play {
+    #[serde(rename = "move")]
+    placement: Move,
+  },
+  /// A piece was added to the end of the queue
+  NewPiece { piece: String },
+  /// Stop calculating the current position
+  Stop,
+  /// Exit the bot
+  Quit,
+}
+
+/// Messages sent from a bot to the frontend
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotMessage {
+  /// Sent once the bot has launched
+  Info {
+    name: String,
+    version: String,
+    author: String,
+    features: Vec<String>,
+  },
+  /// The bot accepts the rules
+  Ready,
+  /// The bot can't play with the rules
+  Error { reason: String },
+  /// Moves the bot would make, best first
+  Suggestion { moves: Vec<Move> },
+}
+
+/// A position to begin calculating from
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Start {
+  pub hold: Option<String>,
+  /// the current piece followed by the previews
+  pub queue: Vec<String>,
+  pub combo: u32,
+  pub back_to_back: bool,
+  /// rows of cells from the bottom up
+  pub board: Vec<Vec<Cell>>,
+}
+
+/// Where a piece is placed and how it got there
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Move {
+  pub location: PieceLocation,
+  pub spin: Spin,
+}
+
+/// The type, orientation and center of a piece, with y counting up from the bottom row
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PieceLocation {
+  #[serde(rename = "type")]
+  pub piece: String,
+  pub orientation: Orientation,
+  pub x: i32,
+  pub y: i32,
+}
+
+/// The rotation of a piece from its spawn state
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation { North, East, South, West }
+
+impl Orientation {
+  pub const ALL: [Orientation; 4] = [Orientation::North, Orientation::East, Orientation::South, Orientation::West];
+}
+
+/// The kind of spin a move ended with
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Spin { None, Mini, Full }
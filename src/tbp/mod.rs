@@ -0,0 +1,178 @@
+use crate::ai::field::Field;
+use crate::ai::search::Pose;
+use crate::algorithm::Grid;
+use crate::constants::piece::{Shape, ShapeType};
+use crate::engine::utility::types::{Coordinate, Size2};
+use crate::tbp::message::{Cell, Orientation, PieceLocation};
+
+/**
+ * Tetris Bot Protocol, so external bots can play our boards and our AI can play elsewhere
+ */
+
+pub mod external;
+pub mod message;
+pub mod server;
+
+/// Rows in a board sent to a bot
+pub const BOARD_ROWS: usize = 40;
+
+/// Name of the cells filled by garbage or landed pieces
+pub const FILLED_CELL: &str = "G";
+
+/// Get the name of `shape_type` used in messages
+pub fn piece_name(shape_type: ShapeType) -> String {
+  String::from(shape_type.name())
+}
+
+/// Get the shape type named `name` in a message
+pub fn parse_piece(name: &str) -> Option<ShapeType> {
+  ShapeType::from_name(name)
+}
+
+/// Get the minos of `shape_type` facing north around its center, with y up
+///
+/// returns `None` for pieces other than the standard Tetriminos, which the protocol can't describe
+fn north_minos(shape_type: ShapeType) -> Option<[Coordinate; 4]> {
+  let mino = Coordinate::new;
+  match shape_type {
+    ShapeType::I => Some([mino(-1, 0), mino(0, 0), mino(1, 0), mino(2, 0)]),
+    ShapeType::J => Some([mino(-1, 1), mino(-1, 0), mino(0, 0), mino(1, 0)]),
+    ShapeType::L => Some([mino(1, 1), mino(-1, 0), mino(0, 0), mino(1, 0)]),
+    ShapeType::O => Some([mino(0, 0), mino(1, 0), mino(0, 1), mino(1, 1)]),
+    ShapeType::S => Some([mino(-1, 0), mino(0, 0), mino(0, 1), mino(1, 1)]),
+    ShapeType::T => Some([mino(-1, 0), mino(0, 0), mino(1, 0), mino(0, 1)]),
+    ShapeType::Z => Some([mino(-1, 1), mino(0, 1), mino(0, 0), mino(1, 0)]),
+    _ => None,
+  }
+}
+
+/// Rotate `mino` clockwise from north to `orientation`
+fn orient(mino: Coordinate, orientation: Orientation) -> Coordinate {
+  match orientation {
+    Orientation::North => mino,
+    Orientation::East => Coordinate::new(mino.y, -mino.x),
+    Orientation::South => Coordinate::new(-mino.x, -mino.y),
+    Orientation::West => Coordinate::new(-mino.y, mino.x),
+  }
+}
+
+/// Sort `cells` top to bottom then left to right
+fn sorted(mut cells: Vec<Coordinate>) -> Vec<Coordinate> {
+  cells.sort_by_key(|cell| (cell.y, cell.x));
+  cells
+}
+
+/// Find the offset that moves `shape` onto `cells`, if it covers them exactly
+fn offset_onto(shape: Vec<Coordinate>, cells: &[Coordinate]) -> Option<Coordinate> {
+  let shape = sorted(shape);
+  let (first, target) = (shape.first()?, cells.first()?);
+  let offset = Coordinate::new(target.x - first.x, target.y - first.y);
+  let covers = shape.len() == cells.len() && shape.iter().zip(cells).all(|(cell, target)| *cell + offset == *target);
+  covers.then_some(offset)
+}
+
+/// Flip the y of `cell` between the bottom up rows of a message and the top down rows of a field `height` tall
+fn flip(cell: Coordinate, height: u32) -> Coordinate {
+  Coordinate::new(cell.x, height as i32 - 1 - cell.y)
+}
+
+/// Get the location of a piece of `shape_type` at `pose` on a field `height` tall
+pub fn pose_to_location(shape_type: ShapeType, pose: &Pose, height: u32) -> Option<PieceLocation> {
+  let shape: &Shape = &shape_type.data().shape[pose.rotation];
+  let cells = sorted(shape.iter().map(|cell| flip(*cell + pose.position, height)).collect());
+  let north = north_minos(shape_type)?;
+
+  Orientation::ALL.iter().find_map(|orientation| {
+    let minos = north.map(|mino| orient(mino, *orientation)).to_vec();
+    offset_onto(minos, &cells).map(|center| PieceLocation {
+      piece: piece_name(shape_type),
+      orientation: *orientation,
+      x: center.x,
+      y: center.y,
+    })
+  })
+}
+
+/// Get the shape type and pose of the piece at `location` on a field `height` tall
+pub fn location_to_pose(location: &PieceLocation, height: u32) -> Option<(ShapeType, Pose)> {
+  let shape_type = parse_piece(&location.piece)?;
+  let center = Coordinate::new(location.x, location.y);
+  let cells = sorted(north_minos(shape_type)?
+    .iter()
+    .map(|mino| flip(orient(*mino, location.orientation) + center, height))
+    .collect());
+
+  shape_type.data().shape
+    .iter()
+    .enumerate()
+    .find_map(|(rotation, shape)| offset_onto(shape.clone(), &cells).map(|position| Pose::new(rotation, position)))
+    .map(|pose| (shape_type, pose))
+}
+
+/// Get the rows of a message board, bottom up, from `field`
+pub fn field_to_board(field: &Field) -> Vec<Vec<Cell>> {
+  let size = field.size();
+  (0..BOARD_ROWS as i32)
+    .map(|row| {
+      let y = size.y as i32 - 1 - row;
+      (0..size.x as i32)
+        .map(|x| field.is_occupied(&Coordinate::new(x, y)).then(|| String::from(FILLED_CELL)))
+        .collect()
+    })
+    .collect()
+}
+
+/// Get a field of `size` from the rows of a message board, ignoring rows above the field
+pub fn board_to_field(board: &[Vec<Cell>], size: Size2) -> Field {
+  let mut field = Field::new(size);
+  let height = size.y;
+  for (row, cells) in board.iter().enumerate().take(height as usize) {
+    for (x, cell) in cells.iter().enumerate() {
+      if cell.is_some() {
+        field.fill(&flip(Coordinate::new(x as i32, row as i32), height));
+      }
+    }
+  }
+  field
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const HEIGHT: u32 = 40;
+
+  #[test]
+  fn locates_a_piece_by_its_center_counting_up() {
+    // the T spawns pointing down, its flat side on the second row from the floor
+    let location = pose_to_location(ShapeType::T, &Pose::new(0, Coordinate::new(3, 37)), HEIGHT).unwrap();
+    assert_eq!(location, PieceLocation { piece: String::from("T"), orientation: Orientation::South, x: 4, y: 1 });
+  }
+
+  #[test]
+  fn round_trips_every_standard_piece_and_rotation() {
+    for shape_type in ShapeType::STANDARD {
+      for rotation in 0..shape_type.data().shape.len() {
+        let pose = Pose::new(rotation, Coordinate::new(3, 30));
+        let location = pose_to_location(shape_type, &pose, HEIGHT)
+          .unwrap_or_else(|| panic!("failed to locate {} in rotation {rotation}", shape_type.name()));
+        assert_eq!(location_to_pose(&location, HEIGHT), Some((shape_type, pose)), "{location:?}");
+      }
+    }
+  }
+
+  #[test]
+  fn round_trips_a_field() {
+    let size = Size2::new(10, HEIGHT);
+    let mut field = Field::new(size);
+    for coordinate in [Coordinate::new(0, 39), Coordinate::new(9, 39), Coordinate::new(4, 38)] {
+      field.fill(&coordinate);
+    }
+
+    let board = field_to_board(&field);
+    assert_eq!(board.len(), BOARD_ROWS);
+    assert_eq!(board[0][0].as_deref(), Some(FILLED_CELL));
+    assert_eq!(board[1][4].as_deref(), Some(FILLED_CELL));
+    assert_eq!(board_to_field(&board, size), field);
+  }
+}
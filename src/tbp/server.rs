@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+use crate::ai::best_placement;
+use crate::ai::evaluate::Weights;
+use crate::ai::field::Field;
+use crate::ai::search::Pose;
+use crate::constants::game::{FIRST_ROW, MATRIX_DIMENSIONS};
+use crate::constants::piece::{DEFAULT_ROTATION, ShapeType};
+use crate::piece::spawn_position;
+use crate::tbp::{board_to_field, location_to_pose, parse_piece, pose_to_location};
+use crate::tbp::message::{BotMessage, FrontendMessage, Move, Spin, Start};
+
+/**
+ * Serve our AI to a frontend as a bot
+ */
+
+/// A position the frontend has started
+struct Game {
+  field: Field,
+  queue: VecDeque<ShapeType>,
+}
+
+impl Game {
+  /// Instantiate a game from a start message
+  fn new(start: &Start) -> Self {
+    Self {
+      field: board_to_field(&start.board, MATRIX_DIMENSIONS),
+      queue: start.queue.iter().filter_map(|piece| parse_piece(piece)).collect(),
+    }
+  }
+
+  /// Suggest the best move for the current piece, if it can be placed
+  fn suggest(&self, weights: &Weights) -> Vec<Move> {
+    let Some(current) = self.queue.front().copied() else {
+      return Vec::new();
+    };
+
+    let start = Pose::new(DEFAULT_ROTATION, spawn_position(&current, MATRIX_DIMENSIONS.x, FIRST_ROW));
+    best_placement(&self.field, current, start, self.queue.get(1).copied(), FIRST_ROW, weights)
+      .and_then(|placement| pose_to_location(current, &placement.pose, MATRIX_DIMENSIONS.y))
+      .map(|location| Move { location, spin: Spin::None })
+      .into_iter()
+      .collect()
+  }
+
+  /// Lock the piece of `placement` and advance the queue
+  fn play(&mut self, placement: &Move) {
+    if let Some((shape_type, pose)) = location_to_pose(&placement.location, MATRIX_DIMENSIONS.y) {
+      self.field.lock(&shape_type.data().shape[pose.rotation], &pose.position);
+    }
+    self.queue.pop_front();
+  }
+}
+
+/// Answer the messages of a frontend on `input` until it quits or closes the stream
+pub fn serve(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+  send(&mut output, &BotMessage::Info {
+    name: String::from(env!("CARGO_PKG_NAME")),
+    version: String::from(env!("CARGO_PKG_VERSION")),
+    author: String::from(env!("CARGO_PKG_AUTHORS")),
+    features: Vec::new(),
+  })?;
+
+  let weights = Weights::default();
+  let mut game: Option<Game> = None;
+  for line in input.lines() {
+    // messages we don't understand are ignored
+    let Ok(message) = serde_json::from_str::<FrontendMessage>(&line?) else {
+      continue;
+    };
+
+    match message {
+      FrontendMessage::Rules => send(&mut output, &BotMessage::Ready)?,
+      FrontendMessage::Start(start) => game = Some(Game::new(&start)),
+      FrontendMessage::Suggest => {
+        let moves = game.as_ref().map_or_else(Vec::new, |game| game.suggest(&weights));
+        send(&mut output, &BotMessage::Suggestion { moves })?;
+      }
+      FrontendMessage::Play { placement } => {
+        if let Some(game) = &mut game {
+          game.play(&placement);
+        }
+      }
+      FrontendMessage::NewPiece { piece } => {
+        if let (Some(game), Some(piece)) = (&mut game, parse_piece(&piece)) {
+          game.queue.push_back(piece);
+        }
+      }
+      FrontendMessage::Stop => game = None,
+      FrontendMessage::Quit => break,
+    }
+  }
+
+  Ok(())
+}
+
+/// Write `message` as a line of JSON
+fn send(output: &mut impl Write, message: &BotMessage) -> io::Result<()> {
+  let json = serde_json::to_string(message).map_err(io::Error::from)?;
+  writeln!(output, "{json}")?;
+  output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Cursor;
+
+  use super::*;
+
+  #[test]
+  fn answers_the_rules_and_suggests_a_move() {
+    let empty = serde_json::to_string(&vec![vec![None::<String>; 10]; 40]).unwrap();
+    let input = [
+      String::from(r#"{"type":"rules"}"#),
+      format!(r#"{{"type":"start","hold":null,"queue":["O","T"],"combo":0,"back_to_back":false,"board":{empty}}}"#),
+      String::from(r#"{"type":"suggest"}"#),
+      String::from(r#"{"type":"quit"}"#),
+    ].join("\n");
+
+    let mut output = Vec::new();
+    serve(Cursor::new(input), &mut output).unwrap();
+    let replies: Vec<BotMessage> = String::from_utf8(output).unwrap()
+      .lines()
+      .map(|line| serde_json::from_str(line).unwrap())
+      .collect();
+
+    assert_eq!(replies.len(), 3);
+    assert!(matches!(replies[0], BotMessage::Info { .. }));
+    assert!(matches!(replies[1], BotMessage::Ready));
+    let BotMessage::Suggestion { moves } = &replies[2] else {
+      panic!("expected a suggestion, got {:?}", replies[2]);
+    };
+    // on an empty field, the O lies flat on the floor
+    let location = &moves.first().expect("failed to suggest a move").location;
+    assert_eq!(location.piece, "O");
+    assert_eq!(location.y, 0);
+  }
+
+  #[test]
+  fn suggests_nothing_without_a_position() {
+    let mut output = Vec::new();
+    serve(Cursor::new(r#"{"type":"suggest"}"#), &mut output).unwrap();
+    let reply = String::from_utf8(output).unwrap().lines().last().map(String::from).unwrap();
+    assert!(matches!(serde_json::from_str(&reply).unwrap(), BotMessage::Suggestion { moves } if moves.is_empty()));
+  }
+}
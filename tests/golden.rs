@@ -0,0 +1,71 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use tetris::constants::controls::PLAYER_ONE_CONTROLS;
+use tetris::constants::game::{BOARD_DIMENSIONS, BUFFER_ROWS, GARBAGE_TILE_ID, TILE_SIZE};
+use tetris::constants::piece::ShapeType;
+use tetris::constants::window::{LETTERBOX_COLOR, SCREEN_COLOR, SCREEN_PIXELS, TITLE};
+use tetris::engine::asset::{AssetManager, AssetType};
+use tetris::engine::geometry::Vec2;
+use tetris::engine::render::{Properties, Renderer, Scaling};
+use tetris::engine::render::golden::compare_golden;
+use tetris::engine::tile::tile::TileId;
+use tetris::engine::tile::tileset::Tileset;
+use tetris::engine::utility::types::Coordinate;
+use tetris::player::{Controller, Player};
+
+/**
+ * Render known boards and their HUD headless, comparing them against the golden images in `tests/golden`
+ */
+
+/// How far each channel of a pixel may be off from the golden image
+const TOLERANCE: u8 = 2;
+
+/// Build the properties of a headless renderer the size of the screen
+fn properties() -> Properties {
+  Properties {
+    title: String::from(TITLE),
+    dimensions: SCREEN_PIXELS,
+    position: None,
+    logical: Some(SCREEN_PIXELS),
+    scaling: Scaling::Integer,
+    fullscreen: false,
+    resizable: false,
+    show_cursor: false,
+    vsync: false,
+    opengl: false,
+    software_acceleration: true,
+    hardware_acceleration: false,
+    screen_color: SCREEN_COLOR,
+    letterbox_color: LETTERBOX_COLOR,
+  }
+}
+
+#[test]
+fn renders_a_board_and_hud() {
+  let mut renderer = Renderer::headless(properties()).expect("failed to build headless renderer");
+  let ttf_context = sdl2::ttf::init().expect("failed to initialize ttf");
+  let mut assets = AssetManager::new(&renderer, &ttf_context);
+  assets.load(AssetType::Texture, String::from("asset/spritesheet.png")).expect("failed to load texture");
+  assets.load(AssetType::Typeface { font_size: 5 }, String::from("asset/typeface.ttf")).expect("failed to load typeface");
+  let texture = assets.textures.use_store().get("spritesheet").expect("failed to fetch texture").clone();
+  let tileset = Rc::new(Tileset::new(texture, Vec2::new(TILE_SIZE, TILE_SIZE)));
+
+  // three rows of garbage with a well on the right, and a few pieces locked on top
+  let bottom = (BUFFER_ROWS + BOARD_DIMENSIONS.y) as i32 - 1;
+  let mut cells: Vec<(Coordinate, TileId)> = (0..3)
+    .flat_map(|row| (0..BOARD_DIMENSIONS.x as i32 - 1).map(move |x| (Coordinate::new(x, bottom - row), GARBAGE_TILE_ID)))
+    .collect();
+  cells.extend([(0, 3), (1, 3), (2, 3), (1, 4)].map(|(x, row)| (Coordinate::new(x, bottom - row), ShapeType::T.data().tile_id)));
+  cells.extend([(5, 3), (6, 3), (5, 4), (6, 4)].map(|(x, row)| (Coordinate::new(x, bottom - row), ShapeType::O.data().tile_id)));
+
+  let controller = Controller::Keyboard(PLAYER_ONE_CONTROLS);
+  let mut player = Player::new(tileset, controller, Vec2::default(), BOARD_DIMENSIONS, BUFFER_ROWS);
+  player.start_setup(&cells, &[ShapeType::L, ShapeType::I], false);
+
+  renderer.clear();
+  player.render(&assets, &mut renderer, true);
+  renderer.present();
+
+  compare_golden(&renderer, Path::new("tests/golden/board.png"), TOLERANCE).unwrap();
+}